@@ -0,0 +1,15 @@
+#![no_main]
+
+use aoc::days::day11::Day11;
+use aoc::Solution;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+  let Ok(monkeys) = Day11::parse(input) else {
+    return;
+  };
+
+  let rendered = monkeys.iter().map(ToString::to_string);
+  let rendered = rendered.collect::<Vec<_>>().join("\n\n");
+  assert_eq!(Day11::parse(&rendered).unwrap(), monkeys);
+});
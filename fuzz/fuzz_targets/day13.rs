@@ -0,0 +1,15 @@
+#![no_main]
+
+use aoc::days::day13::Day13;
+use aoc::Solution;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+  let Ok(pairs) = Day13::parse(input) else {
+    return;
+  };
+
+  let rendered = pairs.iter().map(|(a, b)| format!("{a}\n{b}"));
+  let rendered = rendered.collect::<Vec<_>>().join("\n\n");
+  assert_eq!(Day13::parse(&rendered).unwrap(), pairs);
+});
@@ -0,0 +1,16 @@
+#![no_main]
+
+use aoc::days::day5::Day5;
+use aoc::Solution;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+  let Ok(parsed) = Day5::parse(input) else {
+    return;
+  };
+
+  let (cargo, instructions) = &parsed;
+  let instructions = instructions.iter().map(ToString::to_string);
+  let rendered = format!("{cargo}\n\n{}", instructions.collect::<Vec<_>>().join("\n"));
+  assert_eq!(Day5::parse(&rendered).unwrap(), parsed);
+});
@@ -0,0 +1,15 @@
+#![no_main]
+
+use aoc::days::day7::Day7;
+use aoc::Solution;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+  let Ok(fs) = Day7::parse(input) else {
+    return;
+  };
+
+  // the transcript can't be recovered from the tree, but it must be consistent
+  fs.size("/").unwrap();
+  let _ = fs.to_string();
+});
@@ -0,0 +1,221 @@
+//! A global allocator which counts allocations, enabled with the `count-allocs` feature:
+//!
+//! ```text
+//! $ cargo run --release --features count-allocs -- 13 13-bump
+//! ```
+//!
+//! Counts are kept per thread, so that days running in parallel don't see each other's allocations.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use std::fmt;
+
+use serde::Serialize;
+
+#[cfg(feature = "count-allocs")]
+#[global_allocator]
+static GLOBAL: Counting = Counting;
+
+/// Wraps the [`System`] allocator, counting on behalf of the current thread.
+pub struct Counting;
+
+#[derive(Clone, Copy, Debug, Default)]
+struct Counters {
+  count: u64,
+  bytes: u64,
+  /// Bytes allocated and not yet freed by this thread. Negative if it freed memory allocated
+  /// by another thread.
+  live: i64,
+  peak: i64,
+}
+
+thread_local! {
+  // no destructor, so that using it from inside the allocator never allocates
+  static COUNTERS: Cell<Counters> = const {
+    Cell::new(Counters {
+      count: 0,
+      bytes: 0,
+      live: 0,
+      peak: 0,
+    })
+  };
+}
+
+fn record(allocated: usize, freed: usize) {
+  // the thread local is gone while the thread shuts down
+  let _ = COUNTERS.try_with(|counters| {
+    let mut c = counters.get();
+    if allocated > 0 {
+      c.count += 1;
+      c.bytes += allocated as u64;
+    }
+    c.live += allocated as i64 - freed as i64;
+    c.peak = c.peak.max(c.live);
+    counters.set(c);
+  });
+}
+
+unsafe impl GlobalAlloc for Counting {
+  unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+    let ptr = System.alloc(layout);
+    if !ptr.is_null() {
+      record(layout.size(), 0);
+    }
+    ptr
+  }
+
+  unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+    let ptr = System.alloc_zeroed(layout);
+    if !ptr.is_null() {
+      record(layout.size(), 0);
+    }
+    ptr
+  }
+
+  unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+    System.dealloc(ptr, layout);
+    record(0, layout.size());
+  }
+
+  unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+    let new = System.realloc(ptr, layout, new_size);
+    if !new.is_null() {
+      record(new_size, layout.size());
+    }
+    new
+  }
+}
+
+/// Allocations made while running something.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct Allocs {
+  /// Number of allocations, including reallocations.
+  pub count: u64,
+  /// Total bytes requested.
+  pub bytes: u64,
+  /// The most bytes that were allocated at once, on top of what was already allocated before.
+  pub peak: u64,
+}
+
+/// Whether allocations are being counted, i.e. the `count-allocs` feature is enabled.
+pub fn is_enabled() -> bool {
+  cfg!(feature = "count-allocs")
+}
+
+/// Runs `f`, counting the allocations it makes on this thread if [`is_enabled`].
+pub fn measure<F, R>(f: F) -> (R, Option<Allocs>)
+where
+  F: FnOnce() -> R,
+{
+  if !is_enabled() {
+    return (f(), None);
+  }
+  count(f)
+}
+
+fn count<F, R>(f: F) -> (R, Option<Allocs>)
+where
+  F: FnOnce() -> R,
+{
+  let before = COUNTERS.with(|counters| {
+    let mut c = counters.get();
+    c.peak = c.live;
+    counters.set(c);
+    c
+  });
+  let r = f();
+  let after = COUNTERS.with(Cell::get);
+
+  let allocs = Allocs {
+    count: after.count - before.count,
+    bytes: after.bytes - before.bytes,
+    peak: (after.peak - before.live).max(0) as u64,
+  };
+  (r, Some(allocs))
+}
+
+impl fmt::Display for Allocs {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(
+      f,
+      "{} allocations, {} total, {} peak",
+      self.count,
+      Bytes(self.bytes),
+      Bytes(self.peak)
+    )
+  }
+}
+
+struct Bytes(u64);
+
+impl fmt::Display for Bytes {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let mut size = self.0 as f64;
+    for unit in ["B", "KiB", "MiB"] {
+      if size < 1024.0 {
+        return match unit {
+          "B" => write!(f, "{} {unit}", self.0),
+          _ => write!(f, "{size:.1} {unit}"),
+        };
+      }
+      size /= 1024.0;
+    }
+    write!(f, "{size:.1} GiB")
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  // calls the allocator directly, so that this works whether or not it's the global allocator
+  fn with_counting<R>(f: impl FnOnce(&Counting) -> R) -> Allocs {
+    count(|| f(&Counting)).1.unwrap()
+  }
+
+  #[test]
+  fn counts() {
+    let small = Layout::from_size_align(16, 8).unwrap();
+    let large = Layout::from_size_align(1000, 8).unwrap();
+
+    let allocs = with_counting(|a| unsafe {
+      let p = a.alloc(small);
+      let q = a.alloc_zeroed(large);
+      a.dealloc(q, large);
+      let p = a.realloc(p, small, 64);
+      a.dealloc(p, Layout::from_size_align(64, 8).unwrap());
+    });
+    assert_eq!(
+      allocs,
+      Allocs {
+        count: 3,
+        bytes: 16 + 1000 + 64,
+        peak: 1016,
+      }
+    );
+
+    // the peak is relative to what was live before
+    let p = unsafe { Counting.alloc(large) };
+    let allocs = with_counting(|a| unsafe {
+      a.dealloc(p, large);
+      let p = a.alloc(small);
+      a.dealloc(p, small);
+    });
+    assert_eq!(allocs.peak, 0);
+    assert_eq!(allocs.count, 1);
+  }
+
+  #[test]
+  fn display() {
+    let allocs = Allocs {
+      count: 12,
+      bytes: 3 * 1024 + 512,
+      peak: 100,
+    };
+    assert_eq!(
+      allocs.to_string(),
+      "12 allocations, 3.5 KiB total, 100 B peak"
+    );
+    assert_eq!(Bytes(5 * 1024 * 1024 * 1024).to_string(), "5.0 GiB");
+  }
+}
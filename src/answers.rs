@@ -0,0 +1,204 @@
+//! Known answers for each day, used to catch regressions.
+//!
+//! An answers file has one section per part. Answers may span multiple lines:
+//!
+//! ```text
+//! [part1]
+//! 13820
+//!
+//! [part2]
+//! ####.#..#.
+//! ...#.#.#..
+//! ```
+
+use std::fmt::{self, Write};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use anyhow::Context;
+
+pub fn default_path(day: u32) -> PathBuf {
+  crate::input::day_dir(day).join("answers.txt")
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Answers {
+  parts: [Option<String>; 2],
+}
+
+impl Answers {
+  /// Loads answers from `path`. A missing file means no answers are known yet.
+  pub fn load(path: &Path) -> anyhow::Result<Self> {
+    if !path.exists() {
+      return Ok(Self::default());
+    }
+
+    std::fs::read_to_string(path)
+      .with_context(|| format!("failed to read `{}`", path.display()))?
+      .parse()
+      .with_context(|| format!("invalid answers file `{}`", path.display()))
+  }
+
+  pub fn save(&self, path: &Path) -> anyhow::Result<()> {
+    std::fs::write(path, self.to_string())
+      .with_context(|| format!("failed to write `{}`", path.display()))
+  }
+
+  pub fn get(&self, part: u8) -> Option<&str> {
+    self.parts[part as usize - 1].as_deref()
+  }
+
+  pub fn set(&mut self, part: u8, answer: impl Into<String>) {
+    self.parts[part as usize - 1] = Some(answer.into());
+  }
+}
+
+impl FromStr for Answers {
+  type Err = anyhow::Error;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    let mut answers = Answers::default();
+    let mut current = None;
+
+    for (n, line) in s.lines().enumerate() {
+      if let Some(header) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+        let part = match header {
+          "part1" => 1,
+          "part2" => 2,
+          _ => anyhow::bail!("line {}: unknown section `{line}`", n + 1),
+        };
+        answers.set(part, "");
+        current = Some(part);
+        continue;
+      }
+
+      let Some(part) = current else {
+        if line.trim().is_empty() {
+          continue;
+        }
+        anyhow::bail!("line {}: answer outside of a `[partN]` section", n + 1);
+      };
+
+      let answer = answers.parts[part as usize - 1].as_mut().unwrap();
+      if !answer.is_empty() {
+        answer.push('\n');
+      }
+      answer.push_str(line);
+    }
+
+    for answer in answers.parts.iter_mut().flatten() {
+      answer.truncate(answer.trim_end().len());
+    }
+
+    Ok(answers)
+  }
+}
+
+impl fmt::Display for Answers {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let mut sections = self
+      .parts
+      .iter()
+      .enumerate()
+      .filter_map(|(i, answer)| Some((i + 1, answer.as_ref()?)))
+      .peekable();
+
+    while let Some((part, answer)) = sections.next() {
+      writeln!(f, "[part{part}]")?;
+      writeln!(f, "{answer}")?;
+      if sections.peek().is_some() {
+        writeln!(f)?;
+      }
+    }
+
+    Ok(())
+  }
+}
+
+/// A line-by-line diff of `expected` against `actual`, with `-` marking expected lines and `+`
+/// marking actual lines.
+pub fn diff(expected: &str, actual: &str) -> String {
+  let (expected, actual) = (
+    expected.lines().collect::<Vec<_>>(),
+    actual.lines().collect::<Vec<_>>(),
+  );
+
+  let mut out = String::new();
+  for i in 0..expected.len().max(actual.len()) {
+    match (expected.get(i), actual.get(i)) {
+      (Some(e), Some(a)) if e == a => writeln!(out, "  {e}").unwrap(),
+      (e, a) => {
+        if let Some(e) = e {
+          writeln!(out, "- {e}").unwrap();
+        }
+        if let Some(a) = a {
+          writeln!(out, "+ {a}").unwrap();
+        }
+      }
+    }
+  }
+  out
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  const DAY10: &str = "\
+[part1]
+13820
+
+[part2]
+####.#..#.
+...#.#.#..
+";
+
+  #[test]
+  fn round_trips() {
+    let answers = DAY10.parse::<Answers>().unwrap();
+    assert_eq!(answers.get(1), Some("13820"));
+    assert_eq!(answers.get(2), Some("####.#..#.\n...#.#.#.."));
+    assert_eq!(answers.to_string(), DAY10);
+    assert_eq!(answers.to_string().parse::<Answers>().unwrap(), answers);
+
+    let mut only_part2 = Answers::default();
+    only_part2.set(2, "7");
+    assert_eq!(only_part2.to_string(), "[part2]\n7\n");
+    assert_eq!(
+      only_part2.to_string().parse::<Answers>().unwrap(),
+      only_part2
+    );
+    assert_eq!("".parse::<Answers>().unwrap(), Answers::default());
+  }
+
+  #[test]
+  fn trailing_whitespace() {
+    let answers = "\n[part1]\n42  \n\n\n[part2]\n a \n b\t\n\n"
+      .parse::<Answers>()
+      .unwrap();
+    assert_eq!(answers.get(1), Some("42"));
+    // only the end of an answer is trimmed, other lines keep their whitespace
+    assert_eq!(answers.get(2), Some(" a \n b"));
+  }
+
+  #[test]
+  fn invalid() {
+    let e = "[part3]\n1".parse::<Answers>().unwrap_err();
+    assert_eq!(e.to_string(), "line 1: unknown section `[part3]`");
+    let e = "\n42\n[part1]".parse::<Answers>().unwrap_err();
+    assert_eq!(
+      e.to_string(),
+      "line 2: answer outside of a `[partN]` section"
+    );
+  }
+
+  #[test]
+  fn mismatch() {
+    assert_eq!(diff("13820", "13821"), "- 13820\n+ 13821\n");
+    assert_eq!(
+      diff("####\n#..#\n####", "####\n#.##"),
+      "  ####\n- #..#\n+ #.##\n- ####\n"
+    );
+    assert_eq!(diff("1\n2", "1\n2"), "  1\n  2\n");
+  }
+}
@@ -0,0 +1,159 @@
+use std::fmt;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use crate::alloc::{self, Allocs};
+
+#[derive(Clone, Copy, Debug)]
+pub struct Config {
+  /// Number of untimed runs before measuring.
+  pub warmup: usize,
+  /// Number of timed runs.
+  pub runs: usize,
+}
+
+/// Timings of a single part, with parsing measured separately from solving.
+#[derive(Clone, Debug)]
+pub struct Report {
+  pub answer: String,
+  pub parse: Stats,
+  pub solve: Stats,
+  /// Allocations of a single parse, if they're being counted.
+  pub parse_allocs: Option<Allocs>,
+  /// Allocations of a single solve, if they're being counted.
+  pub solve_allocs: Option<Allocs>,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Stats {
+  pub runs: usize,
+  pub min: Duration,
+  pub median: Duration,
+  pub mean: Duration,
+  pub p95: Duration,
+}
+
+impl Stats {
+  pub fn new(mut samples: Vec<Duration>) -> Self {
+    assert!(!samples.is_empty(), "cannot compute stats without samples");
+    samples.sort_unstable();
+
+    let runs = samples.len();
+    let median = if runs.is_multiple_of(2) {
+      (samples[runs / 2 - 1] + samples[runs / 2]) / 2
+    } else {
+      samples[runs / 2]
+    };
+    // nearest-rank percentile
+    let p95 = samples[(runs * 95).div_ceil(100) - 1];
+
+    Self {
+      runs,
+      min: samples[0],
+      median,
+      mean: samples.iter().sum::<Duration>() / runs as u32,
+      p95,
+    }
+  }
+}
+
+/// Runs `f` `config.warmup` times, then measures it `config.runs` times.
+///
+/// Also returns the output of the first run, and the allocations it made if they're being
+/// counted, so that nothing has to run again to get them.
+pub fn measure<F, R>(config: &Config, mut f: F) -> (Stats, R, Option<Allocs>)
+where
+  F: FnMut() -> R,
+{
+  let runs = config.runs.max(1);
+  let mut samples = Vec::with_capacity(runs);
+
+  // the first run is also the first sample, unless it's a warmup
+  let ((output, elapsed), allocs) = alloc::measure(|| {
+    let start = Instant::now();
+    let r = f();
+    (r, Instant::now() - start)
+  });
+  if config.warmup == 0 {
+    samples.push(elapsed);
+  }
+
+  for _ in 1..config.warmup {
+    black_box(f());
+  }
+
+  while samples.len() < runs {
+    let start = Instant::now();
+    let r = f();
+    samples.push(Instant::now() - start);
+    drop(black_box(r));
+  }
+
+  (Stats::new(samples), output, allocs)
+}
+
+impl fmt::Display for Stats {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(
+      f,
+      "min {:.2?}, median {:.2?}, mean {:.2?}, p95 {:.2?} ({} runs)",
+      self.min, self.median, self.mean, self.p95, self.runs
+    )
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn stats(micros: &[u64]) -> Stats {
+    Stats::new(micros.iter().map(|&n| Duration::from_micros(n)).collect())
+  }
+
+  #[test]
+  fn odd_runs() {
+    let stats = stats(&[50, 10, 30, 20, 40]);
+    assert_eq!(stats.runs, 5);
+    assert_eq!(stats.min, Duration::from_micros(10));
+    assert_eq!(stats.median, Duration::from_micros(30));
+    assert_eq!(stats.mean, Duration::from_micros(30));
+    assert_eq!(stats.p95, Duration::from_micros(50));
+  }
+
+  #[test]
+  fn even_runs() {
+    // 20 runs of 1..=20µs: the 95th percentile is the 19th run
+    let stats = stats(&(1..=20).rev().collect::<Vec<_>>());
+    assert_eq!(stats.min, Duration::from_micros(1));
+    assert_eq!(stats.median, Duration::from_nanos(10_500));
+    assert_eq!(stats.mean, Duration::from_nanos(10_500));
+    assert_eq!(stats.p95, Duration::from_micros(19));
+  }
+
+  #[test]
+  fn single_run() {
+    let stats = stats(&[7]);
+    assert_eq!(stats.runs, 1);
+    assert_eq!(
+      [stats.min, stats.median, stats.mean, stats.p95],
+      [Duration::from_micros(7); 4]
+    );
+  }
+
+  #[test]
+  fn measures_once_without_warmup() {
+    let mut calls = 0;
+    let (stats, output, _) = measure(&Config { warmup: 0, runs: 1 }, || {
+      calls += 1;
+      calls
+    });
+    assert_eq!((stats.runs, output, calls), (1, 1, 1));
+
+    let mut calls = 0;
+    let (stats, output, _) = measure(&Config { warmup: 3, runs: 5 }, || {
+      calls += 1;
+      calls
+    });
+    assert_eq!((stats.runs, output, calls), (5, 1, 8));
+  }
+}
@@ -0,0 +1,605 @@
+//! Talks to adventofcode.com on behalf of the logged in user.
+//!
+//! Requests go through the [`Http`] trait, so that tests can answer them with a stub.
+
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use anyhow::Context;
+
+use crate::guesses::Guesses;
+
+pub const BASE_URL: &str = "https://adventofcode.com";
+pub const YEAR: u32 = 2022;
+
+const USER_AGENT: &str = "github.com/jprochazk/aoc2022";
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Response {
+  pub status: u16,
+  pub body: String,
+}
+
+/// The HTTP layer. Error statuses are returned as responses, only transport failures are errors.
+pub trait Http {
+  fn get(&self, url: &str, session: &str) -> anyhow::Result<Response>;
+  /// Posts `form` URL-encoded.
+  fn post(&self, url: &str, session: &str, form: &[(&str, &str)]) -> anyhow::Result<Response>;
+}
+
+/// Sends real requests.
+pub struct Ureq {
+  agent: ureq::Agent,
+}
+
+impl Default for Ureq {
+  fn default() -> Self {
+    Self {
+      agent: ureq::AgentBuilder::new().user_agent(USER_AGENT).build(),
+    }
+  }
+}
+
+impl Http for Ureq {
+  fn get(&self, url: &str, session: &str) -> anyhow::Result<Response> {
+    let request = self
+      .agent
+      .get(url)
+      .set("Cookie", &format!("session={session}"));
+    into_response(request.call())
+  }
+
+  fn post(&self, url: &str, session: &str, form: &[(&str, &str)]) -> anyhow::Result<Response> {
+    let request = self
+      .agent
+      .post(url)
+      .set("Cookie", &format!("session={session}"));
+    into_response(request.send_form(form))
+  }
+}
+
+fn into_response(result: Result<ureq::Response, ureq::Error>) -> anyhow::Result<Response> {
+  let response = match result {
+    Ok(response) | Err(ureq::Error::Status(_, response)) => response,
+    Err(e) => return Err(e.into()),
+  };
+  Ok(Response {
+    status: response.status(),
+    body: response.into_string()?,
+  })
+}
+
+/// Reads the session token from `AOC_SESSION`, or from the `.session` file in the repository root.
+pub fn session() -> anyhow::Result<String> {
+  if let Ok(session) = std::env::var("AOC_SESSION") {
+    return Ok(session.trim().to_owned());
+  }
+
+  let path = Path::new(env!("CARGO_MANIFEST_DIR")).join(".session");
+  let session = fs::read_to_string(&path).with_context(|| {
+    format!(
+      "no session token, set `AOC_SESSION` or write it to `{}`",
+      path.display()
+    )
+  })?;
+  Ok(session.trim().to_owned())
+}
+
+/// Whether a non-empty input is already at `path`.
+pub fn is_cached(path: &Path) -> bool {
+  fs::read_to_string(path).is_ok_and(|input| !input.trim().is_empty())
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Fetched {
+  /// The input was already on disk, so nothing was requested.
+  Cached(PathBuf),
+  Downloaded(PathBuf),
+}
+
+/// How the server judged a submitted answer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Verdict {
+  Correct,
+  TooHigh,
+  TooLow,
+  /// Wrong, without saying in which direction.
+  Wrong,
+  /// Another answer was submitted too recently, with the remaining wait like `"34s"`.
+  RateLimited(Option<String>),
+  /// The part is already solved, or not unlocked yet.
+  WrongLevel,
+}
+
+impl Verdict {
+  /// Whether the answer is definitely wrong, and so shouldn't be submitted again.
+  pub fn is_wrong(&self) -> bool {
+    matches!(self, Verdict::TooHigh | Verdict::TooLow | Verdict::Wrong)
+  }
+
+  /// Reads the verdict from the page the server responds with.
+  pub fn from_page(page: &str) -> anyhow::Result<Self> {
+    let text = article_text(page);
+
+    let verdict = if text.contains("That's the right answer") {
+      Verdict::Correct
+    } else if text.contains("That's not the right answer") {
+      if text.contains("too high") {
+        Verdict::TooHigh
+      } else if text.contains("too low") {
+        Verdict::TooLow
+      } else {
+        Verdict::Wrong
+      }
+    } else if text.contains("You gave an answer too recently") {
+      let wait = text
+        .split_once("You have ")
+        .and_then(|(_, rest)| rest.split_once(" left to wait"))
+        .map(|(wait, _)| wait.to_owned());
+      Verdict::RateLimited(wait)
+    } else if text.contains("You don't seem to be solving the right level") {
+      Verdict::WrongLevel
+    } else {
+      anyhow::bail!("unexpected response: {}", text.trim());
+    };
+
+    Ok(verdict)
+  }
+}
+
+/// The text of the page's `<article>`, which holds the message, with all tags removed.
+fn article_text(page: &str) -> String {
+  let article = page
+    .split_once("<article>")
+    .and_then(|(_, rest)| rest.split_once("</article>"))
+    .map_or(page, |(article, _)| article);
+
+  let mut text = String::new();
+  let mut in_tag = false;
+  for c in article.chars() {
+    match c {
+      '<' => in_tag = true,
+      '>' => in_tag = false,
+      c if !in_tag => text.push(c),
+      _ => {}
+    }
+  }
+  text
+}
+
+/// Recorded in guesses files, so only wrong verdicts have a name.
+impl fmt::Display for Verdict {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Verdict::Correct => write!(f, "correct"),
+      Verdict::TooHigh => write!(f, "too-high"),
+      Verdict::TooLow => write!(f, "too-low"),
+      Verdict::Wrong => write!(f, "wrong"),
+      Verdict::RateLimited(_) => write!(f, "rate-limited"),
+      Verdict::WrongLevel => write!(f, "wrong-level"),
+    }
+  }
+}
+
+impl FromStr for Verdict {
+  type Err = anyhow::Error;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    match s {
+      "too-high" => Ok(Verdict::TooHigh),
+      "too-low" => Ok(Verdict::TooLow),
+      "wrong" => Ok(Verdict::Wrong),
+      _ => anyhow::bail!("unknown verdict `{s}`"),
+    }
+  }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Submission {
+  pub verdict: Verdict,
+  /// `false` if the verdict was already known from an earlier guess, so nothing was sent.
+  pub sent: bool,
+}
+
+pub struct Client<H = Ureq> {
+  http: H,
+  base_url: String,
+  session: String,
+}
+
+impl Client {
+  /// A client for adventofcode.com, using the session token from [`session`].
+  pub fn from_env() -> anyhow::Result<Self> {
+    Ok(Self::new(Ureq::default(), BASE_URL, session()?))
+  }
+}
+
+impl<H: Http> Client<H> {
+  pub fn new(http: H, base_url: impl Into<String>, session: impl Into<String>) -> Self {
+    Self {
+      http,
+      base_url: base_url.into(),
+      session: session.into(),
+    }
+  }
+
+  fn url(&self, day: u32, path: &str) -> String {
+    format!(
+      "{}/{YEAR}/day/{day}{path}",
+      self.base_url.trim_end_matches('/')
+    )
+  }
+
+  /// Downloads the input of `day` to `path`, unless a non-empty input is already there.
+  pub fn fetch_input(&self, day: u32, path: &Path) -> anyhow::Result<Fetched> {
+    if is_cached(path) {
+      return Ok(Fetched::Cached(path.into()));
+    }
+
+    let response = self.http.get(&self.url(day, "/input"), &self.session)?;
+    match response.status {
+      200 => {}
+      404 => anyhow::bail!("day {day} is not unlocked yet"),
+      400 | 500 if response.body.contains("log in") => {
+        anyhow::bail!("the session token was rejected, it may have expired")
+      }
+      status => anyhow::bail!(
+        "failed to download day {day} input ({status}): {}",
+        response.body.trim()
+      ),
+    }
+
+    // write to a temporary file first, so that an interrupted write is never mistaken for a cached input
+    if let Some(dir) = path.parent() {
+      fs::create_dir_all(dir)?;
+    }
+    let partial = path.with_extension("partial");
+    fs::write(&partial, response.body)?;
+    fs::rename(&partial, path)?;
+
+    Ok(Fetched::Downloaded(path.into()))
+  }
+
+  /// Submits `answer` to `part` of `day`, unless `guesses` already rule it out.
+  /// Wrong answers are recorded in `guesses`.
+  pub fn submit(
+    &self,
+    day: u32,
+    part: u8,
+    answer: &str,
+    guesses: &mut Guesses,
+  ) -> anyhow::Result<Submission> {
+    if let Some(verdict) = guesses.verdict(part, answer) {
+      return Ok(Submission {
+        verdict,
+        sent: false,
+      });
+    }
+
+    let level = part.to_string();
+    let form = [("level", level.as_str()), ("answer", answer)];
+    let response = self
+      .http
+      .post(&self.url(day, "/answer"), &self.session, &form)?;
+    if response.status != 200 {
+      anyhow::bail!(
+        "failed to submit day {day} part {part} ({}): {}",
+        response.status,
+        article_text(&response.body).trim()
+      );
+    }
+
+    let verdict = Verdict::from_page(&response.body)?;
+    if verdict.is_wrong() {
+      guesses.record(part, answer, verdict.clone());
+    }
+    Ok(Submission {
+      verdict,
+      sent: true,
+    })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use std::cell::RefCell;
+  use std::io::{BufRead, BufReader, Read, Write};
+  use std::net::TcpListener;
+  use std::thread;
+
+  use super::*;
+
+  #[derive(Debug, PartialEq, Eq)]
+  struct Request {
+    method: &'static str,
+    url: String,
+    session: String,
+    form: Vec<(String, String)>,
+  }
+
+  #[derive(Default)]
+  struct Stub {
+    responses: RefCell<Vec<Response>>,
+    requests: RefCell<Vec<Request>>,
+  }
+
+  impl Stub {
+    fn new<S: Into<String>>(responses: impl IntoIterator<Item = (u16, S)>) -> Self {
+      let responses = responses
+        .into_iter()
+        .map(|(status, body)| Response {
+          status,
+          body: body.into(),
+        })
+        .collect();
+      Self {
+        responses: RefCell::new(responses),
+        ..Default::default()
+      }
+    }
+
+    fn respond(&self, request: Request) -> anyhow::Result<Response> {
+      self.requests.borrow_mut().push(request);
+      Ok(self.responses.borrow_mut().remove(0))
+    }
+  }
+
+  impl Http for &Stub {
+    fn get(&self, url: &str, session: &str) -> anyhow::Result<Response> {
+      self.respond(Request {
+        method: "GET",
+        url: url.into(),
+        session: session.into(),
+        form: vec![],
+      })
+    }
+
+    fn post(&self, url: &str, session: &str, form: &[(&str, &str)]) -> anyhow::Result<Response> {
+      self.respond(Request {
+        method: "POST",
+        url: url.into(),
+        session: session.into(),
+        form: form
+          .iter()
+          .map(|(k, v)| (k.to_string(), v.to_string()))
+          .collect(),
+      })
+    }
+  }
+
+  fn temp_path(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("aoc-{}-{name}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    dir.join("input.txt")
+  }
+
+  fn page(message: &str) -> String {
+    format!("<html><body><main><article><p>{message}</p></article></main></body></html>")
+  }
+
+  #[test]
+  fn downloads_once() {
+    let stub = Stub::new([(200, "1\n2\n3\n")]);
+    let client = Client::new(&stub, "https://example.com/", "secret");
+    let path = temp_path("downloads-once");
+
+    assert_eq!(
+      client.fetch_input(6, &path).unwrap(),
+      Fetched::Downloaded(path.clone())
+    );
+    assert_eq!(fs::read_to_string(&path).unwrap(), "1\n2\n3\n");
+    assert_eq!(client.fetch_input(6, &path).unwrap(), Fetched::Cached(path));
+
+    assert_eq!(
+      *stub.requests.borrow(),
+      [Request {
+        method: "GET",
+        url: "https://example.com/2022/day/6/input".into(),
+        session: "secret".into(),
+        form: vec![],
+      }]
+    );
+  }
+
+  #[test]
+  fn replaces_empty_input() {
+    let stub = Stub::new([(200, "abc\n")]);
+    let client = Client::new(&stub, "https://example.com", "secret");
+    let path = temp_path("replaces-empty");
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(&path, "\n").unwrap();
+
+    assert_eq!(
+      client.fetch_input(6, &path).unwrap(),
+      Fetched::Downloaded(path.clone())
+    );
+    assert_eq!(fs::read_to_string(&path).unwrap(), "abc\n");
+  }
+
+  #[test]
+  fn errors_are_not_cached() {
+    let stub = Stub::new([
+      (
+        404,
+        "Please don't repeatedly request this endpoint before it unlocks!",
+      ),
+      (
+        400,
+        "Puzzle inputs differ by user.  Please log in to get your puzzle input.",
+      ),
+    ]);
+    let client = Client::new(&stub, "https://example.com", "secret");
+    let path = temp_path("errors");
+
+    let e = client.fetch_input(25, &path).unwrap_err();
+    assert_eq!(e.to_string(), "day 25 is not unlocked yet");
+    let e = client.fetch_input(25, &path).unwrap_err();
+    assert!(e.to_string().contains("session token was rejected"));
+    assert!(!path.exists());
+  }
+
+  #[test]
+  fn verdicts() {
+    let cases = [
+      (
+        "That's the right answer!  You are <em>one gold star</em> closer to saving your vacation.",
+        Verdict::Correct,
+      ),
+      (
+        "That's not the right answer; your answer is too high.  If you're stuck, make sure you're \
+         using the full input data. Please wait one minute before trying again.",
+        Verdict::TooHigh,
+      ),
+      (
+        "That's not the right answer; your answer is too low.",
+        Verdict::TooLow,
+      ),
+      (
+        "That's not the right answer.  If you're stuck, make sure you're using the full input data.",
+        Verdict::Wrong,
+      ),
+      (
+        "You gave an answer too recently; you have to wait after submitting an answer before \
+         trying again.  You have 34s left to wait. <a href=\"/2022/day/6\">[Return to Day 6]</a>",
+        Verdict::RateLimited(Some("34s".into())),
+      ),
+      (
+        "You don't seem to be solving the right level.  Did you already complete it?",
+        Verdict::WrongLevel,
+      ),
+    ];
+
+    for (message, verdict) in cases {
+      assert_eq!(Verdict::from_page(&page(message)).unwrap(), verdict);
+    }
+    assert!(Verdict::from_page(&page("Something else entirely")).is_err());
+  }
+
+  #[test]
+  fn wrong_answers_are_never_sent_twice() {
+    let too_high = page("That's not the right answer; your answer is too high.");
+    let stub = Stub::new([(200, too_high)]);
+    let client = Client::new(&stub, "https://example.com", "secret");
+    let mut guesses = Guesses::default();
+
+    let submission = client.submit(1, 2, "200044", &mut guesses).unwrap();
+    assert_eq!(
+      submission,
+      Submission {
+        verdict: Verdict::TooHigh,
+        sent: true
+      }
+    );
+    assert_eq!(
+      stub.requests.borrow()[0].form,
+      [
+        ("level".to_owned(), "2".to_owned()),
+        ("answer".to_owned(), "200044".to_owned())
+      ]
+    );
+
+    // the stub has no more responses, so these must not be sent
+    for answer in ["200044", "300000"] {
+      let submission = client.submit(1, 2, answer, &mut guesses).unwrap();
+      assert_eq!(
+        submission,
+        Submission {
+          verdict: Verdict::TooHigh,
+          sent: false
+        }
+      );
+    }
+    assert_eq!(stub.requests.borrow().len(), 1);
+  }
+
+  #[test]
+  fn only_wrong_answers_are_recorded() {
+    let stub = Stub::new(
+      [
+        page("You gave an answer too recently.  You have 1m 2s left to wait."),
+        page("That's the right answer!"),
+      ]
+      .map(|body| (200, body)),
+    );
+    let client = Client::new(&stub, "https://example.com", "secret");
+    let mut guesses = Guesses::default();
+
+    let submission = client.submit(6, 1, "1480", &mut guesses).unwrap();
+    assert_eq!(
+      submission.verdict,
+      Verdict::RateLimited(Some("1m 2s".into()))
+    );
+    let submission = client.submit(6, 1, "1480", &mut guesses).unwrap();
+    assert_eq!(submission.verdict, Verdict::Correct);
+    assert_eq!(guesses, Guesses::default());
+  }
+
+  /// Serves `responses` in order, one per connection, and returns the requests it received.
+  fn serve(responses: Vec<(u16, String)>) -> (String, thread::JoinHandle<Vec<String>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    let handle = thread::spawn(move || {
+      let mut requests = vec![];
+      for (status, body) in responses {
+        let (mut stream, _) = listener.accept().unwrap();
+        let mut request = String::new();
+        let mut reader = BufReader::new(&mut stream);
+        let mut length = 0;
+        loop {
+          let mut line = String::new();
+          reader.read_line(&mut line).unwrap();
+          if line.trim().is_empty() {
+            break;
+          }
+          if let Some((name, value)) = line.split_once(':') {
+            if name.eq_ignore_ascii_case("content-length") {
+              length = value.trim().parse().unwrap();
+            }
+          }
+          request += &line;
+        }
+        let mut content = vec![0; length];
+        reader.read_exact(&mut content).unwrap();
+        request += "\r\n";
+        request += &String::from_utf8(content).unwrap();
+
+        write!(
+          stream,
+          "HTTP/1.1 {status} Whatever\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+          body.len()
+        )
+        .unwrap();
+        requests.push(request);
+      }
+      requests
+    });
+    (url, handle)
+  }
+
+  #[test]
+  fn ureq_against_local_server() {
+    let (url, server) = serve(vec![
+      (200, "bvwbjplbgvbhsrlpgdmjqwftvncz\n".into()),
+      (200, page("That's the right answer!")),
+    ]);
+    let client = Client::new(Ureq::default(), url, "secret");
+    let path = temp_path("ureq");
+
+    client.fetch_input(6, &path).unwrap();
+    assert_eq!(
+      fs::read_to_string(&path).unwrap(),
+      "bvwbjplbgvbhsrlpgdmjqwftvncz\n"
+    );
+    let submission = client.submit(6, 1, "5", &mut Guesses::default()).unwrap();
+    assert_eq!(submission.verdict, Verdict::Correct);
+
+    let requests = server.join().unwrap();
+    assert!(requests[0].starts_with("GET /2022/day/6/input HTTP/1.1\r\n"));
+    assert!(requests[0].contains("Cookie: session=secret\r\n"));
+    assert!(requests[1].starts_with("POST /2022/day/6/answer HTTP/1.1\r\n"));
+    assert!(requests[1].contains("Cookie: session=secret\r\n"));
+    assert!(requests[1].ends_with("\r\n\r\nlevel=1&answer=5"));
+  }
+}
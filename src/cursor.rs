@@ -0,0 +1,273 @@
+//! A cursor over the tokens of a [`logos`] lexer, for writing recursive descent parsers.
+
+use std::fmt;
+use std::mem::discriminant;
+use std::ops::Range;
+
+use logos::Logos;
+use thiserror::Error;
+
+use crate::ParseError;
+
+/// A token type a [`Cursor`] can walk over.
+pub trait TokenKind<'a>: Logos<'a, Source = str, Extras = ()> + Clone + fmt::Display {
+  /// Stands in for the end of the input. The lexer should never produce it.
+  const EOF: Self;
+  /// Describes every valid token, for errors about input the lexer doesn't recognize.
+  const EXPECTED: &'static str = "a valid token";
+}
+
+#[derive(Clone, Debug)]
+pub struct Token<T> {
+  pub span: Span,
+  pub kind: T,
+}
+
+impl<T> Token<T> {
+  /// Whether this token is a `kind`, ignoring any data it holds, so that `Int(0)` matches `Int(5)`.
+  #[inline]
+  pub fn is(&self, kind: &T) -> bool {
+    discriminant(&self.kind) == discriminant(kind)
+  }
+}
+
+pub struct Cursor<'a, T: TokenKind<'a>> {
+  inner: logos::Lexer<'a, T>,
+  previous: Token<T>,
+  current: Token<T>,
+}
+
+impl<'a, T: TokenKind<'a>> Cursor<'a, T> {
+  /// Creates a cursor pointing at the first token of `source`.
+  pub fn new(source: &'a str) -> Result<Self, Error<T>> {
+    let eof = Token {
+      span: (source.len()..source.len()).into(),
+      kind: T::EOF,
+    };
+    let mut cursor = Self {
+      inner: T::lexer(source),
+      previous: eof.clone(),
+      current: eof,
+    };
+    cursor.bump()?;
+    Ok(cursor)
+  }
+
+  pub fn source(&self) -> &'a str {
+    self.inner.source()
+  }
+
+  /// The token before [`Cursor::current`].
+  #[inline]
+  pub fn previous(&self) -> &Token<T> {
+    &self.previous
+  }
+
+  #[inline]
+  pub fn current(&self) -> &Token<T> {
+    &self.current
+  }
+
+  #[inline]
+  pub fn is_eof(&self) -> bool {
+    self.current.is(&T::EOF)
+  }
+
+  /// The text of the current token.
+  pub fn slice(&self) -> &'a str {
+    &self.source()[self.current.span.range()]
+  }
+
+  /// Moves to the next token, returning the one that was current.
+  #[inline]
+  pub fn bump(&mut self) -> Result<&Token<T>, Error<T>> {
+    let next = match self.inner.next() {
+      Some(kind) if discriminant(&kind) == discriminant(&T::ERROR) => {
+        return Err(Error::Invalid(
+          self.inner.slice().to_owned(),
+          self.inner.span().into(),
+        ))
+      }
+      Some(kind) => Token {
+        span: self.inner.span().into(),
+        kind,
+      },
+      None => {
+        let end = self.source().len();
+        Token {
+          span: (end..end).into(),
+          kind: T::EOF,
+        }
+      }
+    };
+    self.previous = std::mem::replace(&mut self.current, next);
+    Ok(&self.previous)
+  }
+
+  /// Moves past the current token if it is a `kind`.
+  #[inline]
+  pub fn bump_if(&mut self, kind: T) -> Result<bool, Error<T>> {
+    if self.current.is(&kind) {
+      self.bump()?;
+      Ok(true)
+    } else {
+      Ok(false)
+    }
+  }
+
+  /// Moves past the current token, which must be a `kind`.
+  #[inline]
+  pub fn expect(&mut self, kind: T) -> Result<&Token<T>, Error<T>> {
+    if self.current.is(&kind) {
+      self.bump()
+    } else {
+      Err(Error::Expected(
+        kind,
+        self.current.kind.clone(),
+        self.current.span,
+      ))
+    }
+  }
+}
+
+#[derive(Clone, Debug, Error)]
+pub enum Error<T: fmt::Display> {
+  #[error(r"expected token `{0}` found `{1}` at {2}")]
+  Expected(T, T, Span),
+  #[error(r"invalid token `{0}` at {1}")]
+  Invalid(String, Span),
+  /// Recursive descent would go deeper than the limit, e.g. to avoid overflowing the stack.
+  #[error(r"nested more than {0} levels deep at {1}")]
+  TooDeep(usize, Span),
+}
+
+impl<'a, T: TokenKind<'a>> Error<T> {
+  /// Converts the error into a diagnostic, where `s` is the text the cursor walked over
+  /// and a subslice of `source`.
+  pub fn into_parse_error(self, source: &str, s: &str) -> ParseError {
+    let (expected, span) = match self {
+      Error::Expected(expected, _, span) => (format!("`{expected}`"), span),
+      Error::Invalid(_, span) => (T::EXPECTED.to_owned(), span),
+      Error::TooDeep(limit, span) => (format!("at most {limit} levels of nesting"), span),
+    };
+    if span.start == s.len() {
+      ParseError::after(source, s, expected)
+    } else {
+      ParseError::new(source, &s[span.range()], expected)
+    }
+  }
+}
+
+/// A byte range in the source, which unlike [`Range`] is `Copy`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Span {
+  pub start: usize,
+  pub end: usize,
+}
+
+impl Span {
+  pub fn range(&self) -> Range<usize> {
+    self.start..self.end
+  }
+}
+
+impl From<Range<usize>> for Span {
+  fn from(value: Range<usize>) -> Self {
+    Span {
+      start: value.start,
+      end: value.end,
+    }
+  }
+}
+
+impl fmt::Display for Span {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{}..{}", self.start, self.end)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[derive(Clone, Copy, Debug, Logos, PartialEq, Eq)]
+  enum Kind {
+    #[token("+")]
+    Plus,
+    #[regex(r"\d+", |l| l.slice().parse())]
+    Int(i64),
+    #[regex(r"\s+", logos::skip)]
+    Whitespace,
+    Eof,
+    #[error]
+    Bad,
+  }
+
+  impl fmt::Display for Kind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+      match self {
+        Kind::Plus => write!(f, "+"),
+        Kind::Int(_) => write!(f, "{{integer}}"),
+        Kind::Whitespace => write!(f, "{{whitespace}}"),
+        Kind::Eof => write!(f, "{{eof}}"),
+        Kind::Bad => write!(f, "{{invalid}}"),
+      }
+    }
+  }
+
+  impl TokenKind<'_> for Kind {
+    const EOF: Self = Kind::Eof;
+    const EXPECTED: &'static str = "`+` or an integer";
+  }
+
+  fn sum(s: &str) -> Result<i64, Error<Kind>> {
+    let mut cursor = Cursor::<Kind>::new(s)?;
+    let mut sum = 0;
+    loop {
+      let Kind::Int(n) = cursor.expect(Kind::Int(0))?.kind else {
+        unreachable!()
+      };
+      sum += n;
+      if !cursor.bump_if(Kind::Plus)? {
+        break;
+      }
+    }
+    cursor.expect(Kind::Eof)?;
+    Ok(sum)
+  }
+
+  #[test]
+  fn walks_tokens() {
+    assert_eq!(sum("1 + 2+3").unwrap(), 6);
+
+    let mut cursor = Cursor::<Kind>::new("1 +").unwrap();
+    assert_eq!(cursor.slice(), "1");
+    cursor.bump().unwrap();
+    assert_eq!(cursor.previous().span, Span { start: 0, end: 1 });
+    assert_eq!(cursor.current().span, Span { start: 2, end: 3 });
+    cursor.bump().unwrap();
+    assert!(cursor.is_eof());
+    assert_eq!(cursor.current().span, Span { start: 3, end: 3 });
+  }
+
+  #[test]
+  fn errors() {
+    let source = "sum: 1 + + 2";
+    let s = &source[5..];
+    let error = sum(s).unwrap_err();
+    assert!(matches!(
+      error,
+      Error::Expected(Kind::Int(_), Kind::Plus, _)
+    ));
+    let error = error.into_parse_error(source, s);
+    assert_eq!((error.column, error.found.as_str()), (10, "+"));
+    assert_eq!(error.expected, "`{integer}`");
+
+    let error = sum("1 +").unwrap_err().into_parse_error("1 +", "1 +");
+    assert_eq!((error.column, error.found.as_str()), (4, ""));
+
+    let error = sum("1 - 2").unwrap_err().into_parse_error("1 - 2", "1 - 2");
+    assert_eq!((error.column, error.found.as_str()), (3, "-"));
+    assert_eq!(error.expected, "`+` or an integer");
+  }
+}
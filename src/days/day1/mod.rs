@@ -0,0 +1,538 @@
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::fmt;
+use std::io::{self, BufRead};
+use std::num::IntErrorKind;
+use std::ops::RangeInclusive;
+
+use thiserror::Error;
+
+use crate::gen::Rng;
+use crate::{ParseError, Solution};
+
+pub struct Day1;
+
+impl Solution for Day1 {
+  /// Each elf's total calories, in input order.
+  type Input<'a> = Vec<u64>;
+  type Output1 = u64;
+  /// Three totals that each fit in a `u64` may not fit in one together.
+  type Output2 = u128;
+
+  fn parse(source: &str) -> Result<Self::Input<'_>, ParseError> {
+    let totals = Elves::new(source.as_bytes())
+      .map(|elf| match elf {
+        Ok(elf) => Ok(elf.calories),
+        Err(StreamError::Parse(e)) => Err(e),
+        Err(StreamError::Io(_)) => unreachable!("reading from a slice can't fail"),
+      })
+      .collect::<Result<Vec<_>, _>>()?;
+    if totals.is_empty() {
+      return Err(ParseError::after(source, source, "at least one elf"));
+    }
+    Ok(totals)
+  }
+
+  fn part1(totals: &Self::Input<'_>) -> Self::Output1 {
+    *totals.iter().max().unwrap()
+  }
+
+  fn part2(totals: &Self::Input<'_>) -> Self::Output2 {
+    let mut top = TopK::new(3);
+    for total in totals.iter() {
+      top.push(*total);
+    }
+    top
+      .into_sorted_vec()
+      .iter()
+      .map(|&total| total as u128)
+      .sum()
+  }
+
+  /// `size` elves carrying up to 20 items each.
+  fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+    let elves = (0..size.max(1)).map(|_| {
+      let items = (0..rng.range(1..=20)).map(|_| rng.range(1000..=60_000).to_string());
+      items.collect::<Vec<_>>().join("\n")
+    });
+    Some(elves.collect::<Vec<_>>().join("\n\n"))
+  }
+}
+
+/// Keeps the `k` largest numbers pushed into it, in `O(k)` memory.
+#[derive(Clone, Debug)]
+pub struct TopK {
+  k: usize,
+  // a min-heap, so that the smallest of the top `k` is the one to replace
+  heap: BinaryHeap<Reverse<u64>>,
+}
+
+impl TopK {
+  pub fn new(k: usize) -> Self {
+    Self {
+      k,
+      heap: BinaryHeap::with_capacity(k + 1),
+    }
+  }
+
+  pub fn push(&mut self, n: u64) {
+    if self.heap.len() < self.k {
+      self.heap.push(Reverse(n));
+    } else if let Some(mut min) = self.heap.peek_mut() {
+      if n > min.0 {
+        *min = Reverse(n);
+      }
+    }
+  }
+
+  /// The largest numbers, largest first.
+  pub fn into_sorted_vec(self) -> Vec<u64> {
+    // sorting `Reverse`s in ascending order puts the largest number first
+    self
+      .heap
+      .into_sorted_vec()
+      .into_iter()
+      .map(|n| n.0)
+      .collect()
+  }
+}
+
+#[derive(Debug, Error)]
+pub enum StreamError {
+  #[error(transparent)]
+  Io(#[from] io::Error),
+  #[error(transparent)]
+  Parse(#[from] ParseError),
+}
+
+/// One elf's inventory.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Elf {
+  pub items: usize,
+  pub calories: u64,
+}
+
+/// Reads each elf's inventory from `reader` one line at a time, so that inputs of any size can be
+/// read in constant memory.
+///
+/// Malformed lines are reported with their line number: items which aren't a number or are too
+/// large, whitespace around an item, totals which overflow, and empty elves, i.e. a blank line
+/// which doesn't end an elf. Reading can go on after an error, skipping the line, see
+/// [`skip_malformed`].
+pub struct Elves<R> {
+  reader: R,
+  line: String,
+  /// 1-based number of the last line read.
+  number: usize,
+  /// The elf being read, which is kept across errors.
+  elf: Option<Elf>,
+}
+
+impl<R: BufRead> Elves<R> {
+  pub fn new(reader: R) -> Self {
+    Self {
+      reader,
+      line: String::new(),
+      number: 0,
+      elf: None,
+    }
+  }
+}
+
+impl<R: BufRead> Iterator for Elves<R> {
+  type Item = Result<Elf, StreamError>;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    loop {
+      self.line.clear();
+      match self.reader.read_line(&mut self.line) {
+        Ok(0) => return self.elf.take().map(Ok),
+        Ok(_) => self.number += 1,
+        Err(e) => return Some(Err(e.into())),
+      }
+      let line = self.line.trim_end_matches(['\n', '\r']);
+      // errors are created against the line, so only know its number from here
+      let number = self.number;
+      let error = |mut e: ParseError| {
+        e.line = number;
+        Some(Err(e.into()))
+      };
+
+      if line.is_empty() {
+        return match self.elf.take() {
+          Some(elf) => Some(Ok(elf)),
+          None => error(ParseError::new(
+            line,
+            line,
+            "at least one calorie count per elf",
+          )),
+        };
+      }
+
+      let calories = match item(line) {
+        Ok(calories) => calories,
+        Err(e) => return error(e),
+      };
+      let elf = self.elf.get_or_insert(Elf {
+        items: 0,
+        calories: 0,
+      });
+      match elf.calories.checked_add(calories) {
+        Some(total) => {
+          elf.items += 1;
+          elf.calories = total;
+        }
+        None => {
+          let expected = format!("an elf total of at most {}", u64::MAX);
+          return error(ParseError::new(line, line, expected));
+        }
+      }
+    }
+  }
+}
+
+/// Parses one line of an elf's inventory.
+fn item(line: &str) -> Result<u64, ParseError> {
+  let trimmed = line.trim_start();
+  if trimmed.len() < line.len() {
+    let whitespace = &line[..line.len() - trimmed.len()];
+    return Err(ParseError::new(line, whitespace, "no leading whitespace"));
+  }
+  let trimmed = line.trim_end();
+  if trimmed.len() < line.len() {
+    let whitespace = &line[trimmed.len()..];
+    return Err(ParseError::new(line, whitespace, "no trailing whitespace"));
+  }
+  line.parse::<u64>().map_err(|e| match e.kind() {
+    IntErrorKind::PosOverflow => {
+      let expected = format!("a calorie count of at most {}", u64::MAX);
+      ParseError::new(line, line, expected)
+    }
+    _ => ParseError::new(line, line, "a calorie count"),
+  })
+}
+
+/// Skips malformed lines instead of stopping at them, passing their errors to `warn`.
+pub fn skip_malformed<R: BufRead>(
+  elves: Elves<R>,
+  mut warn: impl FnMut(ParseError),
+) -> impl Iterator<Item = Result<Elf, StreamError>> {
+  elves.filter(move |elf| match elf {
+    Err(StreamError::Parse(e)) => {
+      warn(e.clone());
+      false
+    }
+    _ => true,
+  })
+}
+
+/// The `k` largest elf totals, largest first.
+pub fn top_k<E>(elves: impl IntoIterator<Item = Result<Elf, E>>, k: usize) -> Result<Vec<u64>, E> {
+  let mut top = TopK::new(k);
+  for elf in elves {
+    top.push(elf?.calories);
+  }
+  Ok(top.into_sorted_vec())
+}
+
+/// Statistics about every elf's inventory.
+#[derive(Clone, Debug)]
+pub struct Report {
+  pub elves: usize,
+  pub items: usize,
+  pub min_items: usize,
+  pub max_items: usize,
+  /// Every elf's total calories, smallest first.
+  pub sorted: Vec<u64>,
+  /// The 1-based index and calories of the elves carrying the most, most first. Has more than
+  /// `k` elves when some are tied with the `k`th.
+  pub top: Vec<(usize, u64)>,
+  pub k: usize,
+  /// How many different totals are carried by more than one elf.
+  pub tied_totals: usize,
+  /// How many elves carry a total in each range.
+  pub histogram: Vec<(RangeInclusive<u64>, usize)>,
+}
+
+impl Report {
+  /// Reports on `elves`, which must not be empty, with the top `k` of them and a histogram of
+  /// `bins` bars.
+  pub fn new(elves: &[Elf], k: usize, bins: usize) -> Self {
+    assert!(!elves.is_empty(), "no elves to report on");
+    let mut sorted = elves.iter().map(|elf| elf.calories).collect::<Vec<_>>();
+    sorted.sort_unstable();
+
+    let mut ranked = elves
+      .iter()
+      .enumerate()
+      .map(|(i, elf)| (i + 1, elf.calories))
+      .collect::<Vec<_>>();
+    ranked.sort_by_key(|&(i, calories)| (Reverse(calories), i));
+    let mut top = vec![];
+    for (index, calories) in ranked {
+      if top.len() >= k && top.last().map(|&(_, last)| last) != Some(calories) {
+        break;
+      }
+      top.push((index, calories));
+    }
+
+    let tied_totals = sorted
+      .chunk_by(|a, b| a == b)
+      .filter(|run| run.len() > 1)
+      .count();
+
+    let (min, max) = (sorted[0], sorted[sorted.len() - 1]);
+    // in `u128`, because totals may span all of `u64`
+    let (lo, hi) = (min as u128, max as u128);
+    let bins = bins.max(1) as u128;
+    let width = (hi - lo) / bins + 1;
+    let mut histogram = (0..bins)
+      .map(|bin| lo + bin * width)
+      .take_while(|&start| start <= hi)
+      .map(|start| (start as u64..=(start + width - 1).min(hi) as u64, 0))
+      .collect::<Vec<_>>();
+    for &calories in sorted.iter() {
+      histogram[((calories as u128 - lo) / width) as usize].1 += 1;
+    }
+
+    Self {
+      elves: elves.len(),
+      items: elves.iter().map(|elf| elf.items).sum(),
+      min_items: elves.iter().map(|elf| elf.items).min().unwrap(),
+      max_items: elves.iter().map(|elf| elf.items).max().unwrap(),
+      sorted,
+      top,
+      k,
+      tied_totals,
+      histogram,
+    }
+  }
+
+  pub fn mean(&self) -> f64 {
+    self.sorted.iter().map(|&total| total as u128).sum::<u128>() as f64 / self.elves as f64
+  }
+
+  /// The middle total, or the mean of the two middle ones.
+  pub fn median(&self) -> f64 {
+    let n = self.sorted.len();
+    match n % 2 {
+      1 => self.sorted[n / 2] as f64,
+      _ => (self.sorted[n / 2 - 1] as u128 + self.sorted[n / 2] as u128) as f64 / 2.0,
+    }
+  }
+
+  /// The smallest total that at least `p` percent of elves carry at most (nearest rank).
+  pub fn percentile(&self, p: f64) -> u64 {
+    let rank = (p / 100.0 * self.sorted.len() as f64).ceil() as usize;
+    self.sorted[rank.clamp(1, self.sorted.len()) - 1]
+  }
+}
+
+impl fmt::Display for Report {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let (min, max) = (self.sorted[0], self.sorted[self.sorted.len() - 1]);
+    writeln!(
+      f,
+      "Elves: {}, items: {} ({} to {} per elf, {:.1} on average)",
+      self.elves,
+      self.items,
+      self.min_items,
+      self.max_items,
+      self.items as f64 / self.elves as f64
+    )?;
+    writeln!(
+      f,
+      "Calories: min {min}, max {max}, mean {:.1}, median {}",
+      self.mean(),
+      self.median()
+    )?;
+    let percentiles = [25.0, 50.0, 75.0, 90.0, 99.0]
+      .map(|p| format!("p{p} {}", self.percentile(p)))
+      .join(", ");
+    writeln!(f, "Percentiles: {percentiles}")?;
+
+    writeln!(f, "Top {}:", self.k)?;
+    for (rank, (index, calories)) in self.top.iter().enumerate() {
+      // elves tied with the one before share its rank
+      let tied = rank > 0 && self.top[rank - 1].1 == *calories;
+      let rank = match tied {
+        true => "=".to_owned(),
+        false => format!("{}.", rank + 1),
+      };
+      writeln!(f, "{rank:>6} elf {index}: {calories}")?;
+    }
+    writeln!(
+      f,
+      "Ties: {} total(s) carried by more than one elf",
+      self.tied_totals
+    )?;
+
+    writeln!(f, "Histogram:")?;
+    let most = self.histogram.iter().map(|(_, n)| *n).max().unwrap_or(0);
+    let labels = self
+      .histogram
+      .iter()
+      .map(|(range, _)| format!("{}..={}", range.start(), range.end()))
+      .collect::<Vec<_>>();
+    let label_width = labels.iter().map(String::len).max().unwrap_or(0);
+    let lines = self.histogram.iter().zip(labels).map(|((_, n), label)| {
+      // at least one `#` for any bin that isn't empty
+      let bar = "#".repeat((n * 40).div_ceil(most.max(1)));
+      format!("{label:>label_width$} {bar} {n}")
+    });
+    write!(f, "{}", lines.collect::<Vec<_>>().join("\n"))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  const EXAMPLE: &str = include_str!("example.txt");
+
+  #[test]
+  fn part1() {
+    assert_eq!(Day1::part1(&Day1::parse(EXAMPLE).unwrap()), 24000);
+  }
+
+  #[test]
+  fn part2() {
+    assert_eq!(Day1::part2(&Day1::parse(EXAMPLE).unwrap()), 45000);
+  }
+
+  #[test]
+  fn streams_top_k() {
+    let top = |k| top_k(Elves::new(EXAMPLE.as_bytes()), k).unwrap();
+    assert_eq!(top(1), [24000]);
+    assert_eq!(top(3), [24000, 11000, 10000]);
+    assert_eq!(top(10), [24000, 11000, 10000, 6000, 4000]);
+    assert_eq!(top(0), []);
+
+    let error = top_k(Elves::new("1\n\n2\nx3\n".as_bytes()), 3).unwrap_err();
+    let StreamError::Parse(error) = error else {
+      panic!("{error}")
+    };
+    assert_eq!(
+      (error.line, error.column, error.found.as_str()),
+      (4, 1, "x3")
+    );
+  }
+
+  #[test]
+  fn rejects_malformed_lines() {
+    let error = |input: &str| {
+      let e = Day1::parse(input).unwrap_err();
+      (e.line, e.column, e.found, e.expected)
+    };
+    let max = u64::MAX;
+    assert_eq!(
+      error("1\n2x\n"),
+      (2, 1, "2x".into(), "a calorie count".into())
+    );
+    assert_eq!(
+      error("1\n\n18446744073709551616"),
+      (
+        3,
+        1,
+        "18446744073709551616".into(),
+        format!("a calorie count of at most {max}")
+      )
+    );
+    assert_eq!(
+      error(&format!("{max}\n1")),
+      (2, 1, "1".into(), format!("an elf total of at most {max}"))
+    );
+    assert_eq!(
+      error("1\n\n\n2"),
+      (3, 1, "".into(), "at least one calorie count per elf".into())
+    );
+    assert_eq!(
+      error("\n1"),
+      (1, 1, "".into(), "at least one calorie count per elf".into())
+    );
+    assert_eq!(
+      error("1\n2 \t\n"),
+      (2, 2, " \t".into(), "no trailing whitespace".into())
+    );
+    assert_eq!(
+      error("1\n\n  2"),
+      (3, 1, "  ".into(), "no leading whitespace".into())
+    );
+    assert_eq!(error("").3, "at least one elf");
+
+    // windows line endings are fine
+    assert_eq!(Day1::parse("1\r\n2\r\n\r\n3\r\n").unwrap(), [3, 3]);
+  }
+
+  #[test]
+  fn skips_malformed_lines() {
+    let input = "1\n2x\n3\n\n\n4 \n5\n";
+    let mut warnings = vec![];
+    let elves = skip_malformed(Elves::new(input.as_bytes()), |e| warnings.push(e.line))
+      .collect::<Result<Vec<_>, _>>()
+      .unwrap();
+    assert_eq!(
+      elves,
+      [
+        Elf {
+          items: 2,
+          calories: 4
+        },
+        Elf {
+          items: 1,
+          calories: 5
+        }
+      ]
+    );
+    assert_eq!(warnings, [2, 5, 6]);
+  }
+
+  #[test]
+  fn report() {
+    let elves = Elves::new(EXAMPLE.as_bytes())
+      .collect::<Result<Vec<_>, _>>()
+      .unwrap();
+    let report = Report::new(&elves, 2, 4);
+    assert_eq!((report.mean(), report.median()), (11000.0, 10000.0));
+    assert_eq!(report.percentile(25.0), 6000);
+    assert_eq!(report.percentile(100.0), 24000);
+    assert_eq!(
+      report.to_string(),
+      "\
+Elves: 5, items: 10 (1 to 3 per elf, 2.0 on average)
+Calories: min 4000, max 24000, mean 11000.0, median 10000
+Percentiles: p25 6000, p50 10000, p75 11000, p90 24000, p99 24000
+Top 2:
+    1. elf 4: 24000
+    2. elf 3: 11000
+Ties: 0 total(s) carried by more than one elf
+Histogram:
+  4000..=9000 ######################################## 2
+ 9001..=14001 ######################################## 2
+14002..=19002  0
+19003..=24000 #################### 1"
+    );
+
+    let tied = [30, 10, 20, 20, 20].map(|calories| Elf { items: 1, calories });
+    let report = Report::new(&tied, 2, 1);
+    assert_eq!(report.top, [(1, 30), (3, 20), (4, 20), (5, 20)]);
+    assert_eq!(report.tied_totals, 1);
+    assert_eq!(report.histogram, [(10..=30, 5)]);
+  }
+
+  #[test]
+  fn huge_totals() {
+    let elves = [0, u64::MAX, u64::MAX - 1, u64::MAX].map(|calories| Elf { items: 1, calories });
+    let report = Report::new(&elves, 3, 1);
+    assert_eq!(report.histogram, [(0..=u64::MAX, 4)]);
+    assert_eq!(report.median(), u64::MAX as f64);
+    assert_eq!(report.mean(), 0.75 * u64::MAX as f64);
+
+    let report = Report::new(&elves, 3, 2);
+    let half = u64::MAX / 2;
+    assert_eq!(report.histogram, [(0..=half, 1), (half + 1..=u64::MAX, 3)]);
+
+    let totals = elves.map(|elf| elf.calories).to_vec();
+    assert_eq!(Day1::part2(&totals), 3 * u64::MAX as u128 - 1);
+  }
+}
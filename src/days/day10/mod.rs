@@ -0,0 +1,283 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::error::parse_as;
+use crate::gen::Rng;
+use crate::step::Stepper;
+use crate::viz::{Image, Pixel, Rgb};
+use crate::{ParseError, Solution};
+
+macro_rules! noop {
+  () => {
+    |_| {}
+  };
+}
+
+const CRT_WIDTH: usize = 40;
+const CRT_HEIGHT: usize = 6;
+
+pub struct Day10;
+
+impl Solution for Day10 {
+  type Input<'a> = Vec<Op>;
+  type Output1 = i64;
+  type Output2 = String;
+
+  fn parse(source: &str) -> Result<Self::Input<'_>, ParseError> {
+    let input = source.trim();
+
+    input
+      .split('\n')
+      .map(str::trim)
+      .map(|line| line.parse().map_err(|e: ParseError| e.within(source, line)))
+      .collect()
+  }
+
+  fn part1(ops: &Self::Input<'_>) -> Self::Output1 {
+    let mut sum = 0;
+    let mut cpu = Cpu::new();
+
+    for op in ops.iter() {
+      cpu.exec(*op, &mut |state| sum += state.signal().unwrap_or(0));
+    }
+
+    sum
+  }
+
+  fn part2(ops: &Self::Input<'_>) -> Self::Output2 {
+    crt(ops).ascii()
+  }
+
+  /// `size` instructions, so the CRT draws more than its usual six rows.
+  fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+    let ops = (0..size.max(1)).map(|_| match rng.ratio(1, 3) {
+      true => "noop".to_owned(),
+      false => format!("addx {}", rng.range(0..=20) as i64 - 10),
+    });
+    Some(ops.collect::<Vec<_>>().join("\n"))
+  }
+}
+
+/// The image drawn on the CRT by the end of the program.
+pub fn crt(ops: &[Op]) -> Image {
+  let mut lit = Vec::with_capacity(CRT_WIDTH * CRT_HEIGHT);
+
+  let mut cpu = Cpu::new();
+
+  for op in ops.iter() {
+    cpu.exec(*op, &mut |state| lit.push(state.lit()));
+  }
+
+  draw(&lit, lit.len() / CRT_WIDTH)
+}
+
+/// Draws `rows` rows of pixels, leaving out any that weren't drawn yet.
+fn draw(lit: &[bool], rows: usize) -> Image {
+  Image::from_fn(CRT_WIDTH, rows, |x, y| match lit.get(y * CRT_WIDTH + x) {
+    Some(true) => Pixel::new('#', Rgb::GREEN),
+    Some(false) => Pixel::new('.', Rgb::BLACK),
+    None => Pixel::new(' ', Rgb::BLACK),
+  })
+}
+
+/// Steps through the program one clock cycle at a time. Both parts run the same program.
+pub fn stepper(ops: &[Op], _part: u8) -> Box<dyn Stepper + '_> {
+  Box::new(Program {
+    ops,
+    next: 0,
+    cycle: 0,
+    cpu: Cpu::new(),
+    signal: 0,
+    lit: vec![],
+  })
+}
+
+struct Program<'a> {
+  ops: &'a [Op],
+  next: usize,
+  /// Cycles of the op at `next` which are already over.
+  cycle: usize,
+  cpu: Cpu,
+  signal: i64,
+  lit: Vec<bool>,
+}
+
+impl Stepper for Program<'_> {
+  fn step(&mut self) -> bool {
+    let Some(op) = self.ops.get(self.next) else {
+      return false;
+    };
+
+    let (signal, lit) = (&mut self.signal, &mut self.lit);
+    let done = self.cpu.cycle(*op, self.cycle, &mut |state| {
+      *signal += state.signal().unwrap_or(0);
+      lit.push(state.lit());
+    });
+    if done {
+      self.next += 1;
+      self.cycle = 0;
+    } else {
+      self.cycle += 1;
+    }
+    true
+  }
+
+  fn vars(&self) -> Vec<(String, i64)> {
+    vec![
+      ("cycle".to_owned(), self.cpu.state.clock as i64),
+      ("x".to_owned(), self.cpu.state.x),
+      ("signal".to_owned(), self.signal),
+    ]
+  }
+}
+
+impl fmt::Display for Program<'_> {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let rows = self.lit.len().div_ceil(CRT_WIDTH);
+    writeln!(f, "{}", draw(&self.lit, rows))?;
+    match self.ops.get(self.next) {
+      Some(op) if self.cycle > 0 => write!(f, "executing: {op}, cycle {} of 2", self.cycle + 1),
+      Some(op) => write!(f, "next: {op}"),
+      None => write!(f, "done"),
+    }
+  }
+}
+
+#[derive(Clone, Copy)]
+struct State {
+  clock: u64,
+  x: i64,
+}
+
+impl State {
+  /// The signal strength during this cycle, if it's one of the cycles that count.
+  fn signal(&self) -> Option<i64> {
+    let clock = self.clock;
+    (clock >= 20 && (clock - 20).is_multiple_of(40)).then_some(clock as i64 * self.x)
+  }
+
+  /// Whether the sprite covers the pixel drawn during this cycle.
+  fn lit(&self) -> bool {
+    let pixel = ((self.clock - 1) % CRT_WIDTH as u64) as i64;
+    self.x - 1 <= pixel && pixel <= self.x + 1
+  }
+}
+
+/// Runs ops one clock cycle at a time, calling a hook at the start of every cycle.
+struct Cpu {
+  state: State,
+}
+
+impl Cpu {
+  fn new() -> Self {
+    Self {
+      state: State { clock: 1, x: 1 },
+    }
+  }
+
+  fn exec(&mut self, op: Op, hook: &mut impl FnMut(&State)) {
+    let mut cycle = 0;
+    while !self.cycle(op, cycle, hook) {
+      cycle += 1;
+    }
+  }
+
+  /// Runs cycle `cycle` of `op`, counting from 0, and returns whether `op` is done.
+  fn cycle(&mut self, op: Op, cycle: usize, hook: &mut impl FnMut(&State)) -> bool {
+    match (op, cycle) {
+      (Op::Addx(_), 0) => {
+        self.tick(hook, noop!());
+        false
+      }
+      (Op::Addx(n), _) => {
+        self.tick(hook, |c| c.state.x += n);
+        true
+      }
+      (Op::Noop, _) => {
+        self.tick(hook, noop!());
+        true
+      }
+    }
+  }
+
+  fn tick<H: FnOnce(&mut Self)>(&mut self, hook: &mut impl FnMut(&State), mutate: H) {
+    // start cycle
+    hook(&self.state);
+
+    mutate(self);
+    self.state.clock += 1;
+  }
+}
+
+#[derive(Clone, Copy)]
+pub enum Op {
+  Addx(i64),
+  Noop,
+}
+
+impl FromStr for Op {
+  type Err = ParseError;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    use Op::*;
+    match s.split_once(' ') {
+      Some(("addx", arg)) => Ok(Addx(parse_as(s, arg, "an integer")?)),
+      None if s == "noop" => Ok(Noop),
+      _ => {
+        let inst = s.split(' ').next().unwrap();
+        Err(ParseError::new(s, inst, "`addx <n>` or `noop`"))
+      }
+    }
+  }
+}
+
+impl fmt::Display for Op {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Op::Addx(n) => write!(f, "addx {n}"),
+      Op::Noop => write!(f, "noop"),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  const EXAMPLE: &str = include_str!("example.txt");
+
+  #[test]
+  fn part1() {
+    assert_eq!(Day10::part1(&Day10::parse(EXAMPLE).unwrap()), 13140);
+  }
+
+  #[test]
+  fn part2() {
+    let expected = [
+      "##..##..##..##..##..##..##..##..##..##..",
+      "###...###...###...###...###...###...###.",
+      "####....####....####....####....####....",
+      "#####.....#####.....#####.....#####.....",
+      "######......######......######......####",
+      "#######.......#######.......#######.....",
+    ];
+    assert_eq!(
+      Day10::part2(&Day10::parse(EXAMPLE).unwrap()),
+      expected.join("\n")
+    );
+  }
+
+  #[test]
+  fn steps() {
+    let ops = Day10::parse(EXAMPLE).unwrap();
+    let mut program = stepper(&ops, 1);
+    assert!(program.step());
+    assert_eq!(
+      program.to_string(),
+      "#                                       \nexecuting: addx 15, cycle 2 of 2"
+    );
+    while program.step() {}
+    assert_eq!(program.vars()[2], ("signal".to_owned(), 13140));
+    assert_eq!(program.to_string(), format!("{}\ndone", crt(&ops)));
+  }
+}
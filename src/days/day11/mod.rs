@@ -0,0 +1,412 @@
+#![allow(dead_code)]
+
+use std::cmp::Reverse;
+use std::fmt::{self, Debug};
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+use crate::error::parse_as;
+use crate::gen::Rng;
+use crate::step::Stepper;
+use crate::{ParseError, Solution};
+
+pub struct Day11;
+
+impl Solution for Day11 {
+  type Input<'a> = Vec<Monkey>;
+  type Output1 = u64;
+  type Output2 = u64;
+
+  fn parse(source: &str) -> Result<Self::Input<'_>, ParseError> {
+    let input = source.trim();
+
+    let blocks = input.split("\n\n").map(str::trim).collect::<Vec<_>>();
+    blocks
+      .iter()
+      .enumerate()
+      .map(|(i, monkey)| parse_monkey(source, monkey, i, blocks.len()))
+      .collect()
+  }
+
+  fn part1(monkeys: &Self::Input<'_>) -> Self::Output1 {
+    monkey_business(monkeys.clone(), 20, 3)
+  }
+
+  fn part2(monkeys: &Self::Input<'_>) -> Self::Output2 {
+    monkey_business(monkeys.clone(), 10000, 1)
+  }
+
+  /// Eight monkeys holding `size` items between them.
+  fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+    // distinct primes keep the worry levels below `u64::MAX` even when squared
+    let mut primes = [2, 3, 5, 7, 11, 13, 17, 19, 23];
+    rng.shuffle(&mut primes);
+
+    let count = 8;
+    let mut monkeys = (0..count)
+      .map(|id| Monkey {
+        id,
+        items: vec![],
+        op: match rng.range(0..=2) {
+          0 => Op::AddN(rng.range(1..=8)),
+          1 => Op::MulN(rng.range(2..=19)),
+          _ => Op::Square,
+        },
+        test: Test {
+          divisible_by: primes[id],
+          if_true: (id + rng.range(1..=count as u64 - 1) as usize) % count,
+          if_false: (id + rng.range(1..=count as u64 - 1) as usize) % count,
+        },
+        inspected: 0,
+      })
+      .collect::<Vec<_>>();
+    for _ in 0..size.max(count) {
+      monkeys[rng.index(count)].items.push(rng.range(1..=99));
+    }
+    // every monkey needs at least one starting item to be written down
+    for monkey in monkeys.iter_mut().filter(|m| m.items.is_empty()) {
+      monkey.items.push(rng.range(1..=99));
+    }
+
+    let monkeys = monkeys.iter().map(Monkey::to_string).collect::<Vec<_>>();
+    Some(monkeys.join("\n\n"))
+  }
+}
+
+fn monkey_business(monkeys: Vec<Monkey>, rounds: usize, worry_div: u64) -> u64 {
+  let mut game = KeepAway::new(monkeys, worry_div);
+  for _ in 0..rounds {
+    game.round();
+  }
+  game.monkey_business()
+}
+
+struct KeepAway {
+  monkeys: Vec<Monkey>,
+  worry_div: u64,
+  modulo: u64,
+  rounds: usize,
+  temp: Vec<u64>,
+}
+
+impl KeepAway {
+  fn new(monkeys: Vec<Monkey>, worry_div: u64) -> Self {
+    // all divisors are prime, so avoid overflow by using modular arithmetic
+    let modulo = monkeys.iter().map(|m| m.test.divisible_by).product::<u64>();
+    Self {
+      monkeys,
+      worry_div,
+      modulo,
+      rounds: 0,
+      temp: Vec::with_capacity(64),
+    }
+  }
+
+  fn round(&mut self) {
+    let monkeys = &mut self.monkeys;
+    for i in 0..monkeys.len() {
+      self.temp.append(&mut monkeys[i].items);
+      for item in self.temp.iter().cloned() {
+        monkeys[i].inspected += 1;
+        let item = monkeys[i].op.apply(item);
+        let item = item % self.modulo;
+        let item = item / self.worry_div;
+        let target = monkeys[i].test.target(item);
+        monkeys[target].items.push(item);
+      }
+      self.temp.clear();
+    }
+    self.rounds += 1;
+  }
+
+  fn monkey_business(&self) -> u64 {
+    let mut inspected = self.monkeys.iter().map(|m| m.inspected).collect::<Vec<_>>();
+    inspected.sort_by_key(|&n| Reverse(n));
+    inspected[0] * inspected[1]
+  }
+}
+
+/// Steps through the game one round at a time, for 20 rounds in part 1 and 10000 in part 2.
+pub fn stepper(monkeys: &[Monkey], part: u8) -> Box<dyn Stepper> {
+  let (rounds, worry_div) = match part {
+    1 => (20, 3),
+    _ => (10000, 1),
+  };
+  Box::new(Rounds {
+    game: KeepAway::new(monkeys.to_vec(), worry_div),
+    rounds,
+  })
+}
+
+struct Rounds {
+  game: KeepAway,
+  rounds: usize,
+}
+
+impl Stepper for Rounds {
+  fn step(&mut self) -> bool {
+    if self.game.rounds == self.rounds {
+      return false;
+    }
+    self.game.round();
+    true
+  }
+
+  fn vars(&self) -> Vec<(String, i64)> {
+    let mut vars = vec![
+      ("round".to_owned(), self.game.rounds as i64),
+      ("business".to_owned(), self.game.monkey_business() as i64),
+    ];
+    for monkey in self.game.monkeys.iter() {
+      vars.push((format!("inspected{}", monkey.id), monkey.inspected as i64));
+    }
+    vars
+  }
+}
+
+impl fmt::Display for Rounds {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    writeln!(f, "After round {}:", self.game.rounds)?;
+    let lines = self.game.monkeys.iter().map(|monkey| {
+      let items = monkey.items.iter().map(u64::to_string).collect::<Vec<_>>();
+      format!(
+        "Monkey {} inspected {} item(s), holds: {}",
+        monkey.id,
+        monkey.inspected,
+        items.join(", ")
+      )
+    });
+    write!(f, "{}", lines.collect::<Vec<_>>().join("\n"))
+  }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Monkey {
+  id: usize,
+  items: Vec<u64>,
+  op: Op,
+  test: Test,
+  inspected: u64,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Op {
+  AddN(u64),
+  MulN(u64),
+  Square,
+}
+
+impl Op {
+  fn apply(&self, level: u64) -> u64 {
+    match self {
+      Op::AddN(n) => level + n,
+      Op::MulN(n) => level * n,
+      Op::Square => level * level,
+    }
+  }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct Test {
+  divisible_by: u64,
+  if_true: usize,
+  if_false: usize,
+}
+
+impl Test {
+  fn target(&self, item: u64) -> usize {
+    match item.is_multiple_of(self.divisible_by) {
+      true => self.if_true,
+      false => self.if_false,
+    }
+  }
+}
+
+const MONKEY_LINES: [&str; 6] = [
+  "Monkey ",
+  "Starting items: ",
+  "Operation: new = old ",
+  "Test: divisible by ",
+  "If true: throw to monkey ",
+  "If false: throw to monkey ",
+];
+
+/// Parses the monkey at `index`, which may only throw to the other monkeys in `0..count`.
+fn parse_monkey(source: &str, s: &str, index: usize, count: usize) -> Result<Monkey, ParseError> {
+  // Monkey <id>:
+  //   Starting items: <item>,*
+  //   Operation: new = old <op> <op_arg>
+  //   Test: divisible by <divisible_by>
+  //     If true: throw to monkey <if_true>
+  //     If false: throw to monkey <if_false>
+  static REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+      r"(?x)
+        ^Monkey\s(?P<id>\d+):\n\s+
+        Starting\sitems:\s(?P<items>(?:\d+)(?:,\s\d+)*)\n\s+
+        Operation:\snew\s=\sold\s(?P<op>[*+])\s(?P<op_arg>\d+|\w+)\n\s+
+        Test:\sdivisible\sby\s(?P<div>\d+)\n\s+
+        If\strue:\sthrow\sto\smonkey\s(?P<if_true>\d+)\n\s+
+        If\sfalse:\sthrow\sto\smonkey\s(?P<if_false>\d+)$
+    ",
+    )
+    .unwrap()
+  });
+
+  let Some(cap) = REGEX.captures(s) else {
+    // point at the first line that doesn't fit the description
+    let mut lines = s.lines().map(str::trim);
+    for prefix in MONKEY_LINES {
+      match lines.next() {
+        Some(line) if line.starts_with(prefix) => continue,
+        Some(line) => return Err(ParseError::new(source, line, format!("`{prefix}...`"))),
+        None => return Err(ParseError::after(source, s, format!("`{prefix}...`"))),
+      }
+    }
+    let line = lines.next().unwrap_or(s);
+    return Err(ParseError::new(source, line, "a monkey description"));
+  };
+  let field = |name: &str| cap.name(name).unwrap().as_str();
+  let int = |name: &str| parse_as::<u64>(source, field(name), "an integer");
+  let target = |name: &str| match parse_as::<usize>(source, field(name), "a monkey id")? {
+    n if n < count && n != index => Ok(n),
+    _ => Err(ParseError::new(
+      source,
+      field(name),
+      format!("another monkey between 0 and {}", count - 1),
+    )),
+  };
+
+  let id = parse_as(source, field("id"), "a monkey id")?;
+  let items = field("items")
+    .split(", ")
+    .map(|v| parse_as(source, v, "a worry level"))
+    .collect::<Result<_, _>>()?;
+  let op = match (field("op"), field("op_arg")) {
+    ("*", "old") => Op::Square,
+    ("*", _) => Op::MulN(int("op_arg")?),
+    (_, _) => Op::AddN(int("op_arg")?),
+  };
+  let test = Test {
+    divisible_by: int("div")?,
+    if_true: target("if_true")?,
+    if_false: target("if_false")?,
+  };
+  if test.divisible_by == 0 {
+    return Err(ParseError::new(source, field("div"), "a non-zero divisor"));
+  }
+
+  Ok(Monkey {
+    id,
+    items,
+    op,
+    test,
+    inspected: 0,
+  })
+}
+
+impl fmt::Display for Monkey {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let items = self.items.iter().map(u64::to_string).collect::<Vec<_>>();
+    let op = match &self.op {
+      Op::AddN(n) => format!("+ {n}"),
+      Op::MulN(n) => format!("* {n}"),
+      Op::Square => "* old".to_owned(),
+    };
+
+    writeln!(f, "Monkey {}:", self.id)?;
+    writeln!(f, "  Starting items: {}", items.join(", "))?;
+    writeln!(f, "  Operation: new = old {op}")?;
+    writeln!(f, "  Test: divisible by {}", self.test.divisible_by)?;
+    writeln!(f, "    If true: throw to monkey {}", self.test.if_true)?;
+    write!(f, "    If false: throw to monkey {}", self.test.if_false)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use proptest::collection::vec;
+  use proptest::prelude::*;
+
+  use super::*;
+  use crate::testing::mutated;
+
+  const EXAMPLE: &str = include_str!("example.txt");
+
+  #[test]
+  fn part1() {
+    assert_eq!(Day11::part1(&Day11::parse(EXAMPLE).unwrap()), 10605);
+  }
+
+  #[test]
+  fn part2() {
+    assert_eq!(Day11::part2(&Day11::parse(EXAMPLE).unwrap()), 2713310158);
+  }
+
+  #[test]
+  fn steps() {
+    let monkeys = Day11::parse(EXAMPLE).unwrap();
+    let mut rounds = stepper(&monkeys, 1);
+    assert!(rounds.step());
+    assert!(rounds
+      .to_string()
+      .ends_with("Monkey 3 inspected 5 item(s), holds: "));
+    while rounds.step() {}
+    assert_eq!(
+      rounds.vars()[..2],
+      [("round".to_owned(), 20), ("business".to_owned(), 10605)]
+    );
+  }
+
+  fn monkeys() -> impl Strategy<Value = Vec<Monkey>> {
+    (2..8usize).prop_flat_map(|count| {
+      let op = prop_oneof![
+        (0..20u64).prop_map(Op::AddN),
+        (0..20u64).prop_map(Op::MulN),
+        Just(Op::Square),
+      ];
+      let monkey = (vec(0..100u64, 1..6), op, 1..30u64, 1..count, 1..count);
+      vec(monkey, count).prop_map(move |monkeys| {
+        monkeys
+          .into_iter()
+          .enumerate()
+          .map(|(id, (items, op, divisible_by, t, f))| Monkey {
+            id,
+            items,
+            op,
+            test: Test {
+              divisible_by,
+              // offsets from `id`, so monkeys never throw to themselves
+              if_true: (id + t) % count,
+              if_false: (id + f) % count,
+            },
+            inspected: 0,
+          })
+          .collect()
+      })
+    })
+  }
+
+  fn render(monkeys: &[Monkey]) -> String {
+    monkeys
+      .iter()
+      .map(Monkey::to_string)
+      .collect::<Vec<_>>()
+      .join("\n\n")
+  }
+
+  proptest! {
+    #[test]
+    fn monkeys_round_trip(monkeys in monkeys()) {
+      prop_assert_eq!(Day11::parse(&render(&monkeys)).unwrap(), monkeys);
+    }
+
+    #[test]
+    fn mutated_input_fails_or_round_trips(input in mutated(EXAMPLE)) {
+      if let Ok(monkeys) = Day11::parse(&input) {
+        prop_assert_eq!(Day11::parse(&render(&monkeys)).unwrap(), monkeys);
+      }
+    }
+  }
+}
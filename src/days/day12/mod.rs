@@ -0,0 +1,173 @@
+use std::collections::VecDeque;
+
+use crate::gen::Rng;
+use crate::geom::Point;
+use crate::grid::Grid;
+use crate::{ParseError, Solution};
+
+pub struct Day12;
+
+impl Solution for Day12 {
+  type Input<'a> = Heightmap;
+  type Output1 = usize;
+  type Output2 = usize;
+
+  fn parse(source: &str) -> Result<Self::Input<'_>, ParseError> {
+    let input = source.trim();
+    Heightmap::parse(input).map_err(|e| e.within(source, input))
+  }
+
+  fn part1(map: &Self::Input<'_>) -> Self::Output1 {
+    bfs(map, map.start, Cell::End, step_up).expect("checked by `parse`")
+  }
+
+  fn part2(map: &Self::Input<'_>) -> Self::Output2 {
+    bfs(map, map.end, Cell::N(0), step_down).expect("checked by `parse`")
+  }
+
+  /// A `size` rows tall map, at least 52 columns wide. The height rises from `a` on the left
+  /// to `z` on the right, with random walls everywhere except the row from `S` to `E`.
+  fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+    let (cols, rows) = (size.max(52), size.max(1));
+    let path = rng.index(rows);
+    let rows = (0..rows).map(|y| {
+      (0..cols)
+        .map(|x| match x {
+          0 if y == path => 'S',
+          _ if y == path && x == cols - 1 => 'E',
+          // at least 2 columns per height, so the slope is never steeper than 1
+          _ if y == path || rng.ratio(3, 4) => (b'a' + (x * 26 / cols) as u8) as char,
+          _ => (b'a' + rng.range(0..=25) as u8) as char,
+        })
+        .collect::<String>()
+    });
+    Some(rows.collect::<Vec<_>>().join("\n"))
+  }
+}
+
+fn step_up(map: &Heightmap, from: Node, to: Node) -> bool {
+  map.height(to) - map.height(from) <= 1
+}
+
+fn step_down(map: &Heightmap, from: Node, to: Node) -> bool {
+  map.height(from) - map.height(to) <= 1
+}
+
+fn bfs(
+  map: &Heightmap,
+  start: Node,
+  end: Cell,
+  can_access: impl Fn(&Heightmap, Node, Node) -> bool,
+) -> Option<usize> {
+  let mut q: VecDeque<Node> = [start].into_iter().collect();
+  let mut v = Grid::filled(map.grid.rows(), map.grid.cols(), false);
+  v[start.pos] = true;
+
+  while let Some(node) = q.pop_front() {
+    if map.at(node) == Some(end) {
+      return Some(node.dist);
+    }
+
+    for pos in map.grid.neighbors4(node.pos.x, node.pos.y) {
+      let neighbor = Node {
+        pos: pos.into(),
+        dist: node.dist + 1,
+      };
+      if !v[neighbor.pos] && can_access(map, node, neighbor) {
+        v[neighbor.pos] = true;
+        q.push_back(neighbor);
+      }
+    }
+  }
+
+  None
+}
+
+pub struct Heightmap {
+  grid: Grid<Cell>,
+  start: Node,
+  end: Node,
+}
+
+impl Heightmap {
+  fn parse(s: &str) -> Result<Self, ParseError> {
+    let chars = Grid::try_parse(s, "a height `a`-`z`, `S` or `E`", |c| {
+      matches!(c, 'a'..='z' | 'S' | 'E').then_some(c)
+    })?;
+    let node = |(x, y)| Node {
+      pos: Point::new(x, y),
+      dist: 0,
+    };
+    let find = |marker: char| {
+      chars
+        .position(|&c| c == marker)
+        .map(node)
+        .ok_or_else(|| ParseError::after(s, s, format!("a `{marker}` somewhere in the map")))
+    };
+    let start = find('S')?;
+    let end = find('E')?;
+
+    let grid = chars.map(|&cell| match cell {
+      'S' => Cell::N(0),
+      'E' => Cell::End,
+      n => Cell::N((n as u8 - b'a') as i64),
+    });
+
+    let map = Self { grid, start, end };
+    // `S` is at height `a`, so part 2 has a path whenever part 1 does
+    if bfs(&map, start, Cell::End, step_up).is_none() {
+      let line = s.split('\n').nth(end.pos.y).unwrap();
+      let e = &line[end.pos.x..end.pos.x + 1];
+      return Err(ParseError::new(s, e, "an `E` that can be reached from `S`"));
+    }
+
+    Ok(map)
+  }
+
+  fn at(&self, node: Node) -> Option<Cell> {
+    self.grid.get(node.pos.x, node.pos.y).cloned()
+  }
+
+  fn height(&self, node: Node) -> i64 {
+    match self.at(node) {
+      Some(Cell::End) => (b'z' - b'a') as i64,
+      Some(Cell::N(n)) => n,
+      None => i64::MAX,
+    }
+  }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Cell {
+  End,
+  N(i64),
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Node {
+  pos: Point<usize>,
+  dist: usize,
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  const EXAMPLE: &str = include_str!("example.txt");
+
+  #[test]
+  fn part1() {
+    assert_eq!(Day12::part1(&Day12::parse(EXAMPLE).unwrap()), 31);
+  }
+
+  #[test]
+  fn part2() {
+    assert_eq!(Day12::part2(&Day12::parse(EXAMPLE).unwrap()), 29);
+  }
+
+  #[test]
+  fn unreachable() {
+    let e = Day12::parse("Sab\nbcE").err().unwrap();
+    assert_eq!((e.line, e.column), (2, 3));
+  }
+}
@@ -0,0 +1,274 @@
+use core::fmt;
+
+use itertools::Itertools;
+use logos::Logos;
+
+use crate::cursor::{self, Cursor};
+use crate::gen::Rng;
+use crate::{ParseError, Solution};
+
+pub struct Day13;
+
+impl Solution for Day13 {
+  type Input<'a> = Vec<(Packet, Packet)>;
+  type Output1 = usize;
+  type Output2 = usize;
+
+  fn parse(source: &str) -> Result<Self::Input<'_>, ParseError> {
+    let input = source.trim();
+
+    input
+      .split("\n\n")
+      .map(|pair| {
+        let (a, b) = pair
+          .split_once('\n')
+          .ok_or_else(|| ParseError::after(source, pair, "a second packet"))?;
+        let (a, b) = (a.trim(), b.trim());
+        let packet = |s| parse(s).map_err(|e| e.into_parse_error(source, s));
+        Ok((packet(a)?, packet(b)?))
+      })
+      .collect()
+  }
+
+  fn part1(pairs: &Self::Input<'_>) -> Self::Output1 {
+    pairs
+      .iter()
+      .positions(|(a, b)| a < b)
+      .map(|i| i + 1)
+      .sum::<usize>()
+  }
+
+  fn part2(pairs: &Self::Input<'_>) -> Self::Output2 {
+    let dividers = [parse("[[2]]").unwrap(), parse("[[6]]").unwrap()];
+
+    let mut pos = [1, 2];
+
+    for packet in pairs.iter().flat_map(|(a, b)| [a, b]) {
+      if *packet < dividers[0] {
+        pos[0] += 1;
+        pos[1] += 1;
+      } else if *packet < dividers[1] {
+        pos[1] += 1;
+      }
+    }
+
+    pos[0] * pos[1]
+  }
+
+  /// `size` pairs of packets, nested up to `4 + size / 50` lists deep but never more than 16, so
+  /// that the input grows linearly with `size`.
+  fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+    fn packet(rng: &mut Rng, depth: u64) -> Packet {
+      // one nested list per list on average, so packets grow with depth but don't explode
+      let items = (0..rng.range(0..=4))
+        .map(|_| match depth > 0 && rng.ratio(1, 2) {
+          true => packet(rng, depth - 1),
+          false => Packet::Int(rng.range(0..=10)),
+        })
+        .collect();
+      Packet::List(items)
+    }
+
+    let max_depth = (4 + size as u64 / 50).min(16);
+    let pairs = (0..size.max(1)).map(|_| {
+      let (a, b) = (rng.range(1..=max_depth), rng.range(1..=max_depth));
+      format!("{}\n{}", packet(rng, a), packet(rng, b))
+    });
+    Some(pairs.collect::<Vec<_>>().join("\n\n"))
+  }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Packet {
+  List(Vec<Packet>),
+  Int(u64),
+}
+
+impl Ord for Packet {
+  fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+    match (self, other) {
+      (Packet::Int(a), Packet::Int(b)) => a.cmp(b),
+      (Packet::List(a), Packet::List(b)) => a.cmp(b),
+      (Packet::Int(a), Packet::List(b)) => [Packet::Int(*a)][..].cmp(&b[..]),
+      (Packet::List(a), Packet::Int(b)) => a[..].cmp(&[Packet::Int(*b)]),
+    }
+  }
+}
+
+impl PartialOrd for Packet {
+  fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+    Some(self.cmp(other))
+  }
+}
+
+/// Packets nested deeper than this are rejected, instead of overflowing the stack while
+/// parsing, comparing or dropping them.
+pub const MAX_DEPTH: usize = 256;
+
+type Lexer<'a> = Cursor<'a, TokenKind>;
+type Error = cursor::Error<TokenKind>;
+
+fn parse(s: &str) -> Result<Packet, Error> {
+  fn parse_list(lex: &mut Lexer, depth: usize) -> Result<Packet, Error> {
+    use TokenKind::*;
+
+    if depth > MAX_DEPTH {
+      return Err(Error::TooDeep(MAX_DEPTH, lex.current().span));
+    }
+    let mut items = vec![];
+
+    lex.expect(BracketL)?;
+    if lex.bump_if(BracketR)? {
+      return Ok(Packet::List(items));
+    }
+    items.push(parse_packet(lex, depth)?);
+    while lex.bump_if(Comma)? {
+      items.push(parse_packet(lex, depth)?)
+    }
+    lex.expect(BracketR)?;
+
+    Ok(Packet::List(items))
+  }
+  fn parse_int(lex: &mut Lexer) -> Result<Packet, Error> {
+    use TokenKind::*;
+
+    let token = lex.expect(Int(0))?;
+    let Int(n) = token.kind else { unreachable!() };
+    Ok(Packet::Int(n))
+  }
+  fn parse_packet(lex: &mut Lexer, depth: usize) -> Result<Packet, Error> {
+    use TokenKind::*;
+
+    if lex.current().is(&BracketL) {
+      parse_list(lex, depth + 1)
+    } else {
+      parse_int(lex)
+    }
+  }
+
+  let mut lexer = Cursor::new(s)?;
+  let packet = parse_list(&mut lexer, 1)?;
+  lexer.expect(TokenKind::Eof)?;
+  Ok(packet)
+}
+
+#[derive(Clone, Copy, Debug, Logos, PartialEq, Eq)]
+enum TokenKind {
+  #[token("[")]
+  BracketL,
+  #[token("]")]
+  BracketR,
+  #[token(",")]
+  Comma,
+  #[regex(r"\d+", |l| l.slice().parse())]
+  Int(u64),
+
+  #[regex(r"[\s\n\t ]+", logos::skip)]
+  Whitespace,
+  Eof,
+  #[error]
+  Bad,
+}
+
+impl cursor::TokenKind<'_> for TokenKind {
+  const EOF: Self = TokenKind::Eof;
+  const EXPECTED: &'static str = "a packet token";
+}
+
+impl fmt::Display for TokenKind {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(
+      f,
+      "{}",
+      match self {
+        TokenKind::BracketL => "[",
+        TokenKind::BracketR => "]",
+        TokenKind::Comma => ",",
+        TokenKind::Int(_) => "{integer}",
+        TokenKind::Whitespace => "{whitespace}",
+        TokenKind::Eof => "{eof}",
+        TokenKind::Bad => "{invalid}",
+      }
+    )
+  }
+}
+
+impl fmt::Display for Packet {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Packet::List(list) => {
+        write!(f, "[")?;
+        let mut items = list.iter().peekable();
+        while let Some(item) = items.next() {
+          write!(f, "{item}")?;
+          if items.peek().is_some() {
+            write!(f, ",")?;
+          }
+        }
+        write!(f, "]")
+      }
+      Packet::Int(int) => write!(f, "{int}"),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use proptest::collection::vec;
+  use proptest::prelude::*;
+
+  use super::*;
+  use crate::testing::mutated;
+
+  const EXAMPLE: &str = include_str!("example.txt");
+
+  #[test]
+  fn part1() {
+    assert_eq!(Day13::part1(&Day13::parse(EXAMPLE).unwrap()), 13);
+  }
+
+  #[test]
+  fn part2() {
+    assert_eq!(Day13::part2(&Day13::parse(EXAMPLE).unwrap()), 140);
+  }
+
+  #[test]
+  fn too_deep() {
+    let nested = |depth| "[".repeat(depth) + &"]".repeat(depth);
+    let input = format!("{}\n[]", nested(MAX_DEPTH));
+    assert!(Day13::parse(&input).is_ok());
+
+    let input = format!("[]\n{}", nested(200_000));
+    let e = Day13::parse(&input).unwrap_err();
+    assert_eq!((e.line, e.column), (2, MAX_DEPTH + 1));
+    assert!(e.to_string().contains("at most 256 levels of nesting"));
+  }
+
+  fn list() -> impl Strategy<Value = Packet> {
+    let leaf = any::<u64>().prop_map(Packet::Int);
+    let packet = leaf.prop_recursive(4, 64, 8, |inner| vec(inner, 0..8).prop_map(Packet::List));
+    vec(packet, 0..8).prop_map(Packet::List)
+  }
+
+  fn render(pairs: &[(Packet, Packet)]) -> String {
+    pairs
+      .iter()
+      .map(|(a, b)| format!("{a}\n{b}"))
+      .collect::<Vec<_>>()
+      .join("\n\n")
+  }
+
+  proptest! {
+    #[test]
+    fn packets_round_trip(pairs in vec((list(), list()), 1..8)) {
+      prop_assert_eq!(Day13::parse(&render(&pairs)).unwrap(), pairs);
+    }
+
+    #[test]
+    fn mutated_input_fails_or_round_trips(input in mutated(EXAMPLE)) {
+      if let Ok(pairs) = Day13::parse(&input) {
+        prop_assert_eq!(Day13::parse(&render(&pairs)).unwrap(), pairs);
+      }
+    }
+  }
+}
@@ -0,0 +1,242 @@
+use core::fmt;
+
+use bumpalo::collections::Vec;
+use bumpalo::Bump as Arena;
+use itertools::Itertools;
+use logos::Logos;
+
+use crate::cursor::{self, Cursor};
+use crate::days::day13::MAX_DEPTH;
+use crate::gen::Rng;
+use crate::{ParseError, Solution};
+
+pub struct Day13Bump;
+
+impl Solution for Day13Bump {
+  type Input<'a> = std::vec::Vec<(&'a str, &'a str)>;
+  type Output1 = usize;
+  type Output2 = usize;
+
+  fn parse(source: &str) -> Result<Self::Input<'_>, ParseError> {
+    let input = source.trim();
+
+    // packets are parsed by each part, so that arena allocation is part of the measurement,
+    // here they're only parsed into a throwaway arena to reject malformed input
+    let arena = Arena::new();
+    input
+      .split("\n\n")
+      .map(|pair| {
+        let (a, b) = pair
+          .split_once('\n')
+          .ok_or_else(|| ParseError::after(source, pair, "a second packet"))?;
+        let (a, b) = (a.trim(), b.trim());
+        for s in [a, b] {
+          parse(&arena, s).map_err(|e| e.into_parse_error(source, s))?;
+        }
+        Ok((a, b))
+      })
+      .collect()
+  }
+
+  fn part1(pairs: &Self::Input<'_>) -> Self::Output1 {
+    let arena = Arena::new();
+
+    pairs
+      .iter()
+      .map(|(a, b)| (parse(&arena, a).unwrap(), parse(&arena, b).unwrap()))
+      .positions(|(a, b)| a < b)
+      .map(|i| i + 1)
+      .sum::<usize>()
+  }
+
+  fn part2(pairs: &Self::Input<'_>) -> Self::Output2 {
+    let arena = Arena::new();
+
+    let dividers = [
+      parse(&arena, "[[2]]").unwrap(),
+      parse(&arena, "[[6]]").unwrap(),
+    ];
+
+    let mut pos = [1, 2];
+
+    for packet in pairs
+      .iter()
+      .flat_map(|(a, b)| [parse(&arena, a).unwrap(), parse(&arena, b).unwrap()])
+    {
+      if packet < dividers[0] {
+        pos[0] += 1;
+        pos[1] += 1;
+      } else if packet < dividers[1] {
+        pos[1] += 1;
+      }
+    }
+
+    pos[0] * pos[1]
+  }
+
+  fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+    super::day13::Day13::generate(rng, size)
+  }
+}
+
+#[derive(Clone, PartialEq, Eq)]
+pub enum Packet<'bump> {
+  List(Vec<'bump, Packet<'bump>>),
+  Int(u64),
+}
+
+impl<'bump> Ord for Packet<'bump> {
+  fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+    match (self, other) {
+      (Packet::Int(a), Packet::Int(b)) => a.cmp(b),
+      (Packet::List(a), Packet::List(b)) => a.cmp(b),
+      (Packet::Int(a), Packet::List(b)) => [Packet::Int(*a)][..].cmp(&b[..]),
+      (Packet::List(a), Packet::Int(b)) => a[..].cmp(&[Packet::Int(*b)]),
+    }
+  }
+}
+
+impl<'bump> PartialOrd for Packet<'bump> {
+  fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+    Some(self.cmp(other))
+  }
+}
+
+type Lexer<'a> = Cursor<'a, TokenKind>;
+type Error = cursor::Error<TokenKind>;
+
+fn parse<'bump>(arena: &'bump Arena, s: &str) -> Result<Packet<'bump>, Error> {
+  fn parse_list<'bump>(
+    arena: &'bump Arena,
+    lex: &mut Lexer,
+    depth: usize,
+  ) -> Result<Packet<'bump>, Error> {
+    use TokenKind::*;
+
+    if depth > MAX_DEPTH {
+      return Err(Error::TooDeep(MAX_DEPTH, lex.current().span));
+    }
+    let mut items = Vec::new_in(arena);
+
+    lex.expect(BracketL)?;
+    if lex.bump_if(BracketR)? {
+      return Ok(Packet::List(items));
+    }
+    items.push(parse_packet(arena, lex, depth)?);
+    while lex.bump_if(Comma)? {
+      items.push(parse_packet(arena, lex, depth)?)
+    }
+    lex.expect(BracketR)?;
+
+    Ok(Packet::List(items))
+  }
+  fn parse_int<'bump>(_: &'bump Arena, lex: &mut Lexer) -> Result<Packet<'bump>, Error> {
+    use TokenKind::*;
+
+    let token = lex.expect(Int(0))?;
+    let Int(n) = token.kind else { unreachable!() };
+    Ok(Packet::Int(n))
+  }
+  fn parse_packet<'bump>(
+    arena: &'bump Arena,
+    lex: &mut Lexer,
+    depth: usize,
+  ) -> Result<Packet<'bump>, Error> {
+    use TokenKind::*;
+
+    if lex.current().is(&BracketL) {
+      parse_list(arena, lex, depth + 1)
+    } else {
+      parse_int(arena, lex)
+    }
+  }
+
+  let mut lexer = Cursor::new(s)?;
+  let packet = parse_list(arena, &mut lexer, 1)?;
+  lexer.expect(TokenKind::Eof)?;
+  Ok(packet)
+}
+
+#[derive(Clone, Copy, Debug, Logos, PartialEq, Eq)]
+enum TokenKind {
+  #[token("[")]
+  BracketL,
+  #[token("]")]
+  BracketR,
+  #[token(",")]
+  Comma,
+  #[regex(r"\d+", |l| l.slice().parse())]
+  Int(u64),
+
+  #[regex(r"[\s\n\t ]+", logos::skip)]
+  Whitespace,
+  Eof,
+  #[error]
+  Bad,
+}
+
+impl cursor::TokenKind<'_> for TokenKind {
+  const EOF: Self = TokenKind::Eof;
+  const EXPECTED: &'static str = "a packet token";
+}
+
+impl fmt::Display for TokenKind {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(
+      f,
+      "{}",
+      match self {
+        TokenKind::BracketL => "[",
+        TokenKind::BracketR => "]",
+        TokenKind::Comma => ",",
+        TokenKind::Int(_) => "{integer}",
+        TokenKind::Whitespace => "{whitespace}",
+        TokenKind::Eof => "{eof}",
+        TokenKind::Bad => "{invalid}",
+      }
+    )
+  }
+}
+
+impl<'bump> fmt::Display for Packet<'bump> {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Packet::List(list) => {
+        write!(f, "[")?;
+        let mut items = list.iter().peekable();
+        while let Some(item) = items.next() {
+          write!(f, "{item}")?;
+          if items.peek().is_some() {
+            write!(f, ",")?;
+          }
+        }
+        write!(f, "]")
+      }
+      Packet::Int(int) => write!(f, "{int}"),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  const EXAMPLE: &str = include_str!("../day13/example.txt");
+
+  #[test]
+  fn part1() {
+    assert_eq!(Day13Bump::part1(&Day13Bump::parse(EXAMPLE).unwrap()), 13);
+  }
+
+  #[test]
+  fn part2() {
+    assert_eq!(Day13Bump::part2(&Day13Bump::parse(EXAMPLE).unwrap()), 140);
+  }
+
+  #[test]
+  fn too_deep() {
+    let input = format!("[]\n{}", "[".repeat(200_000));
+    let e = Day13Bump::parse(&input).unwrap_err();
+    assert_eq!((e.line, e.column), (2, MAX_DEPTH + 1));
+  }
+}
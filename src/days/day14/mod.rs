@@ -0,0 +1,429 @@
+#![allow(dead_code)]
+
+use std::fmt;
+
+use crate::error::parse_as;
+use crate::gen::Rng;
+use crate::geom::{Bounds, Point};
+use crate::grid::{Grid, SparseGrid};
+use crate::step::Stepper;
+use crate::viz::{Image, Pixel, Recorder, Rgb};
+use crate::{ParseError, Solution};
+
+type Pos = Point<usize>;
+
+pub struct Day14;
+
+impl Solution for Day14 {
+  type Input<'a> = StaticGrid;
+  type Output1 = u64;
+  type Output2 = u64;
+
+  fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+    parse(input)
+  }
+
+  fn part1(grid: &Self::Input<'_>) -> Self::Output1 {
+    let mut grid = grid.clone();
+    simulate(&mut grid, |_| {})
+  }
+
+  fn part2(grid: &Self::Input<'_>) -> Self::Output2 {
+    let mut grid = DynamicGrid::from(grid.clone());
+    simulate(&mut grid, |_| {})
+  }
+
+  /// `size` rock paths, in a cave whose width and depth grow with `size` up to 400 deep.
+  fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+    // sand can't spread wider than the floor is deep, so neither do the rocks
+    let depth = (10 + size as u64).min(400);
+    let paths = (0..size.max(1)).map(|_| {
+      let mut x = rng.range(500 - depth..=500 + depth);
+      let mut y = rng.range(1..=depth);
+      let mut coords = vec![format!("{x},{y}")];
+      for i in 0..rng.range(1..=4) {
+        if i % 2 == 0 {
+          x = rng.range(x.saturating_sub(10).max(500 - depth)..=(x + 10).min(500 + depth));
+        } else {
+          y = rng.range(y.saturating_sub(10).max(1)..=(y + 10).min(depth));
+        }
+        coords.push(format!("{x},{y}"));
+      }
+      coords.join(" -> ")
+    });
+    Some(paths.collect::<Vec<_>>().join("\n"))
+  }
+}
+
+/// Records a frame every time a unit of sand comes to rest.
+pub fn animate(grid: &StaticGrid, part: u8, recorder: &mut Recorder) -> anyhow::Result<u64> {
+  fn run<T: Cave>(mut grid: T, recorder: &mut Recorder) -> anyhow::Result<u64> {
+    let mut result = recorder.record(|| grid.image());
+    let units = simulate(&mut grid, |grid| {
+      if result.is_ok() {
+        result = recorder.record(|| grid.image());
+      }
+    });
+    result?;
+    recorder.write(&grid.image())?;
+    Ok(units)
+  }
+
+  match part {
+    1 => run(grid.clone(), recorder),
+    _ => run(DynamicGrid::from(grid.clone()), recorder),
+  }
+}
+
+/// Simulates sand until it stops coming to rest, calling `on_rest` after every unit that did.
+fn simulate<T: Cave>(grid: &mut T, mut on_rest: impl FnMut(&T)) -> u64 {
+  let mut units = 0;
+
+  // stop at out of bounds, because any future unit of sand will also go out of bounds
+  while let Some(sand) = drop_sand(grid) {
+    units += 1;
+    on_rest(grid);
+
+    if sand == SOURCE {
+      break;
+    }
+  }
+
+  units
+}
+
+const SOURCE: Pos = Pos::new(500, 0);
+
+/// Drops a unit of sand from the source, and returns where it came to rest, or `None` if it fell
+/// out of bounds.
+fn drop_sand<T: Cave>(grid: &mut T) -> Option<Pos> {
+  let mut sand = SOURCE;
+  while let Some(move_) = maybe_move(grid, sand) {
+    match move_ {
+      Move::Coords(pos) => {
+        sand = pos;
+      }
+      Move::OutOfBounds => return None,
+    }
+  }
+
+  // no more moves and not out of bounds = at rest
+  grid.set(sand, Cell::Sand);
+  Some(sand)
+}
+
+/// Steps through one unit of sand at a time, without a floor for part 1 and with one for part 2.
+pub fn stepper(grid: &StaticGrid, part: u8) -> Box<dyn Stepper> {
+  match part {
+    1 => Box::new(Pour::new(grid.clone())),
+    _ => Box::new(Pour::new(DynamicGrid::from(grid.clone()))),
+  }
+}
+
+struct Pour<T> {
+  grid: T,
+  units: u64,
+  last: Option<Pos>,
+  done: bool,
+}
+
+impl<T: Cave> Pour<T> {
+  fn new(grid: T) -> Self {
+    Self {
+      grid,
+      units: 0,
+      last: None,
+      done: false,
+    }
+  }
+}
+
+impl<T: Cave> Stepper for Pour<T> {
+  fn step(&mut self) -> bool {
+    if self.done {
+      return false;
+    }
+    match drop_sand(&mut self.grid) {
+      Some(sand) => {
+        self.units += 1;
+        self.last = Some(sand);
+        self.done = sand == SOURCE;
+        true
+      }
+      None => {
+        self.done = true;
+        false
+      }
+    }
+  }
+
+  fn vars(&self) -> Vec<(String, i64)> {
+    let last = self.last.unwrap_or(SOURCE);
+    vec![
+      ("units".to_owned(), self.units as i64),
+      ("x".to_owned(), last.x as i64),
+      ("y".to_owned(), last.y as i64),
+    ]
+  }
+}
+
+impl<T: Cave> fmt::Display for Pour<T> {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{}", self.grid.image())
+  }
+}
+
+enum Move {
+  Coords(Pos),
+  OutOfBounds,
+}
+
+fn maybe_move<T: Cave>(grid: &T, sand: Pos) -> Option<Move> {
+  let possible_moves = [
+    // below
+    Pos::new(sand.x, sand.y + 1),
+    // below + left
+    Pos::new(sand.x - 1, sand.y + 1),
+    // below + right
+    Pos::new(sand.x + 1, sand.y + 1),
+  ];
+
+  for move_ in possible_moves {
+    match grid.get(move_) {
+      Some(Cell::Air) => return Some(Move::Coords(move_)),
+      Some(Cell::Rock | Cell::Sand) => continue,
+      None => return Some(Move::OutOfBounds),
+    }
+  }
+
+  None
+}
+
+fn parse(source: &str) -> Result<StaticGrid, ParseError> {
+  let mut paths = vec![];
+  let mut bounds = Bounds::new(SOURCE);
+
+  // parse paths
+  for path in source.trim().split('\n') {
+    let mut coords: Vec<Pos> = vec![];
+    for coord in path.split(" -> ") {
+      let (x, y) = coord
+        .split_once(',')
+        .ok_or_else(|| ParseError::new(source, coord, "a coordinate like `498,4`"))?;
+      let pos = Pos::new(
+        parse_as(source, x, "an x coordinate")?,
+        parse_as(source, y, "a y coordinate")?,
+      );
+
+      // paths only ever go straight
+      if let Some(prev) = coords.last().copied() {
+        if prev.x != pos.x && prev.y != pos.y {
+          return Err(ParseError::new(
+            source,
+            coord,
+            "a coordinate in line with the previous one",
+          ));
+        }
+      }
+
+      coords.push(pos);
+      bounds.expand(pos);
+    }
+    paths.push(coords);
+  }
+
+  // init grid
+  let mut grid = StaticGrid::new(bounds);
+  for path in paths {
+    let mut coords = path.iter();
+
+    let mut prev = coords.next().unwrap();
+    grid.set(*prev, Cell::Rock);
+
+    for next in coords {
+      // vertical line
+      if prev.y != next.y {
+        let col = if prev.y < next.y {
+          prev.y..=next.y
+        } else {
+          next.y..=prev.y
+        };
+        for y in col {
+          grid.set((prev.x, y), Cell::Rock);
+        }
+      }
+
+      // horizontal line
+      if prev.x != next.x {
+        let row = if prev.x < next.x {
+          prev.x..=next.x
+        } else {
+          next.x..=prev.x
+        };
+        for x in row {
+          grid.set((x, prev.y), Cell::Rock);
+        }
+      }
+
+      prev = next;
+    }
+  }
+
+  Ok(grid)
+}
+
+#[derive(Clone, Copy, Default, Debug, PartialEq, Eq)]
+enum Cell {
+  #[default]
+  Air,
+  Rock,
+  Sand,
+}
+
+trait Cave: fmt::Display {
+  fn get(&self, pos: impl Into<Pos>) -> Option<Cell>;
+  fn set(&mut self, pos: impl Into<Pos>, cell: Cell);
+  fn image(&self) -> Image;
+}
+
+impl Cave for StaticGrid {
+  fn get(&self, pos: impl Into<Pos>) -> Option<Cell> {
+    let pos = pos.into();
+    let (x, y) = (
+      pos.x.checked_sub(self.bounds.min.x)?,
+      pos.y.checked_sub(self.bounds.min.y)?,
+    );
+    self.cells.get(x, y).cloned()
+  }
+
+  fn set(&mut self, pos: impl Into<Pos>, cell: Cell) {
+    let pos = pos.into();
+    let (x, y) = (pos.x - self.bounds.min.x, pos.y - self.bounds.min.y);
+    self.cells.set(x, y, cell);
+  }
+
+  fn image(&self) -> Image {
+    Image::from_grid(&self.cells, |cell| cell.pixel())
+  }
+}
+
+impl Cave for DynamicGrid {
+  fn get(&self, pos: impl Into<Pos>) -> Option<Cell> {
+    let pos = pos.into();
+    if pos.y >= self.floor_y {
+      return Some(Cell::Rock);
+    }
+
+    Some(*self.cells.get(pos.x as i64, pos.y as i64))
+  }
+
+  fn set(&mut self, pos: impl Into<Pos>, cell: Cell) {
+    let pos = pos.into();
+    self.cells.set(pos.x as i64, pos.y as i64, cell);
+  }
+
+  /// Sand piles up at most as wide as it is high, so this is wide enough to fit every unit of
+  /// sand, and each frame of an animation has the same size.
+  fn image(&self) -> Image {
+    let floor = self.floor_y as i64;
+    let min_x = 500 - floor;
+    let cols = 2 * floor as usize + 1;
+    Image::from_fn(cols, self.floor_y + 1, |x, y| {
+      let (x, y) = (min_x + x as i64, y as i64);
+      if y == floor {
+        Cell::Rock.pixel()
+      } else {
+        self.cells.get(x, y).pixel()
+      }
+    })
+  }
+}
+
+#[derive(Clone)]
+pub struct StaticGrid {
+  cells: Grid<Cell>,
+  bounds: Bounds<usize>,
+}
+
+impl StaticGrid {
+  fn new(bounds: Bounds<usize>) -> Self {
+    Self {
+      cells: Grid::new(bounds.height(), bounds.width()),
+      bounds,
+    }
+  }
+}
+
+struct DynamicGrid {
+  cells: SparseGrid<Cell>,
+  floor_y: usize,
+}
+
+impl From<StaticGrid> for DynamicGrid {
+  fn from(grid: StaticGrid) -> Self {
+    let mut cells = SparseGrid::default();
+
+    for ((x, y), cell) in grid.cells.iter() {
+      let pos = grid.bounds.min + Pos::new(x, y);
+      cells.set(pos.x as i64, pos.y as i64, *cell);
+    }
+
+    Self {
+      cells,
+      floor_y: grid.bounds.max.y + 2,
+    }
+  }
+}
+
+impl Cell {
+  fn pixel(&self) -> Pixel {
+    match self {
+      Cell::Air => Pixel::new('.', Rgb::BLACK),
+      Cell::Rock => Pixel::new('#', Rgb::GRAY),
+      Cell::Sand => Pixel::new('o', Rgb::YELLOW),
+    }
+  }
+}
+
+impl fmt::Display for StaticGrid {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    writeln!(f, "{}", self.image())
+  }
+}
+
+impl fmt::Display for DynamicGrid {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    writeln!(f, "{}", self.image())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  const EXAMPLE: &str = include_str!("example.txt");
+
+  #[test]
+  fn part1() {
+    assert_eq!(Day14::part1(&Day14::parse(EXAMPLE).unwrap()), 24);
+  }
+
+  #[test]
+  fn part2() {
+    assert_eq!(Day14::part2(&Day14::parse(EXAMPLE).unwrap()), 93);
+  }
+
+  #[test]
+  fn steps() {
+    let grid = Day14::parse(EXAMPLE).unwrap();
+    let mut sand = stepper(&grid, 1);
+    assert!(sand.step());
+    assert_eq!(
+      sand.vars()[1..],
+      [("x".to_owned(), 500), ("y".to_owned(), 8)]
+    );
+    while sand.step() {}
+    assert_eq!(sand.vars()[0], ("units".to_owned(), 24));
+    assert!(!sand.step());
+  }
+}
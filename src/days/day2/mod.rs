@@ -0,0 +1,451 @@
+use std::fmt;
+
+use thiserror::Error;
+
+use crate::gen::Rng;
+use crate::{ParseError, Solution};
+
+pub struct Day2;
+
+impl Solution for Day2 {
+  /// The opponent's shape and the column of the response, `0` to `2` for `X` to `Z`.
+  type Input<'a> = Vec<(Shape, usize)>;
+  type Output1 = u64;
+  type Output2 = u64;
+
+  fn parse(source: &str) -> Result<Self::Input<'_>, ParseError> {
+    let input = source.trim();
+
+    input
+      .split('\n')
+      .map(|round| {
+        let (opponent, me) = round
+          .split_once(' ')
+          .ok_or_else(|| ParseError::after(source, round, "a space"))?;
+        let opponent = match opponent {
+          "A" => Shape(0),
+          "B" => Shape(1),
+          "C" => Shape(2),
+          _ => return Err(ParseError::new(source, opponent, "one of `A`, `B` or `C`")),
+        };
+        let column = match me {
+          "X" => 0,
+          "Y" => 1,
+          "Z" => 2,
+          _ => return Err(ParseError::new(source, me, "one of `X`, `Y` or `Z`")),
+        };
+        Ok((opponent, column))
+      })
+      .collect()
+  }
+
+  fn part1(strategy: &Self::Input<'_>) -> Self::Output1 {
+    score(strategy, Interpretation::PART1)
+  }
+
+  fn part2(strategy: &Self::Input<'_>) -> Self::Output2 {
+    score(strategy, Interpretation::PART2)
+  }
+
+  /// `size` rounds.
+  fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+    let rounds = (0..size.max(1)).map(|_| {
+      let opponent = rng.pick(&["A", "B", "C"]);
+      let me = rng.pick(&["X", "Y", "Z"]);
+      format!("{opponent} {me}")
+    });
+    Some(rounds.collect::<Vec<_>>().join("\n"))
+  }
+}
+
+fn score(strategy: &[(Shape, usize)], interpretation: Interpretation) -> u64 {
+  let game = Game::rock_paper_scissors();
+  let mut total = 0;
+  for &(opponent, column) in strategy.iter() {
+    let choice = interpretation.choose(opponent, column, &game);
+
+    total += game.score(choice, opponent);
+  }
+  total
+}
+
+/// A game of shapes where every shape beats half of the others and loses to the other half, like
+/// Rock-Paper-Scissors or Rock-Paper-Scissors-Spock-Lizard.
+///
+/// Shapes are in a circle, and a shape beats the ones an odd number of places behind it, so that
+/// e.g. Paper beats Rock and Rock beats Scissors.
+#[derive(Clone, Debug)]
+pub struct Game {
+  names: Vec<String>,
+  scoring: Scoring,
+}
+
+/// Points for a round: the value of the chosen shape, plus points for the outcome.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Scoring {
+  /// Indexed by shape.
+  pub values: Vec<u64>,
+  pub win: u64,
+  pub draw: u64,
+  pub loss: u64,
+}
+
+impl Scoring {
+  /// Shapes are worth 1, 2, 3, ..., and a win, draw and loss are worth 6, 3 and 0.
+  pub fn standard(shapes: usize) -> Self {
+    Self {
+      values: (1..=shapes as u64).collect(),
+      win: 6,
+      draw: 3,
+      loss: 0,
+    }
+  }
+}
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum GameError {
+  #[error("a game needs an odd number of shapes, at least 3, found {0}")]
+  ShapeCount(usize),
+  #[error("expected a value for each of the {expected} shapes, found {found}")]
+  ValueCount { expected: usize, found: usize },
+}
+
+impl Game {
+  pub fn new<S: Into<String>>(
+    names: impl IntoIterator<Item = S>,
+    scoring: Scoring,
+  ) -> Result<Self, GameError> {
+    let names = names.into_iter().map(Into::into).collect::<Vec<_>>();
+    if names.len() < 3 || names.len() % 2 == 0 {
+      return Err(GameError::ShapeCount(names.len()));
+    }
+    if scoring.values.len() != names.len() {
+      return Err(GameError::ValueCount {
+        expected: names.len(),
+        found: scoring.values.len(),
+      });
+    }
+    Ok(Self { names, scoring })
+  }
+
+  pub fn rock_paper_scissors() -> Self {
+    Self::new(["Rock", "Paper", "Scissors"], Scoring::standard(3)).unwrap()
+  }
+
+  pub fn rock_paper_scissors_spock_lizard() -> Self {
+    let names = ["Rock", "Paper", "Scissors", "Spock", "Lizard"];
+    Self::new(names, Scoring::standard(5)).unwrap()
+  }
+
+  pub fn shapes(&self) -> impl Iterator<Item = Shape> {
+    (0..self.names.len()).map(Shape)
+  }
+
+  pub fn shape(&self, name: &str) -> Option<Shape> {
+    self.names.iter().position(|n| n == name).map(Shape)
+  }
+
+  pub fn name(&self, shape: Shape) -> &str {
+    &self.names[shape.0]
+  }
+
+  pub fn scoring(&self) -> &Scoring {
+    &self.scoring
+  }
+
+  /// The score for choosing `mine` against `theirs`.
+  pub fn score(&self, mine: Shape, theirs: Shape) -> u64 {
+    mine.value(self) + mine.play(theirs, self)
+  }
+}
+
+/// What `X`, `Y` and `Z` in the second column of the strategy guide mean.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Interpretation {
+  Shapes([Shape; 3]),
+  Outcomes([Outcome; 3]),
+}
+
+impl Interpretation {
+  /// How part 1 reads the guide.
+  pub const PART1: Self = Self::Shapes([Shape(0), Shape(1), Shape(2)]);
+  /// How part 2 reads the guide.
+  pub const PART2: Self = Self::Outcomes([Outcome::Lose, Outcome::Draw, Outcome::Win]);
+
+  /// Every mapping of `X`, `Y` and `Z` to distinct shapes or to distinct outcomes.
+  pub fn all() -> Vec<Self> {
+    const PERMUTATIONS: [[usize; 3]; 6] = [
+      [0, 1, 2],
+      [0, 2, 1],
+      [1, 0, 2],
+      [1, 2, 0],
+      [2, 0, 1],
+      [2, 1, 0],
+    ];
+    let outcomes = [Outcome::Lose, Outcome::Draw, Outcome::Win];
+    let shapes = PERMUTATIONS.map(|p| Self::Shapes(p.map(Shape)));
+    let outcomes = PERMUTATIONS.map(|p| Self::Outcomes(p.map(|i| outcomes[i])));
+    shapes.into_iter().chain(outcomes).collect()
+  }
+
+  /// The shape to choose against `them` when the guide says `column`, `0` to `2` for `X` to `Z`.
+  pub fn choose(&self, them: Shape, column: usize, game: &Game) -> Shape {
+    match self {
+      Self::Shapes(shapes) => shapes[column],
+      Self::Outcomes(outcomes) => outcomes[column].choose(them, game),
+    }
+  }
+}
+
+/// The total score of a strategy guide for every [`Interpretation`], best first.
+pub struct Interpretations {
+  game: Game,
+  scores: Vec<(Interpretation, u64)>,
+}
+
+impl Interpretations {
+  pub fn new(strategy: &[(Shape, usize)]) -> Self {
+    let game = Game::rock_paper_scissors();
+
+    // there are only 9 different rounds, so score each of them once per interpretation
+    let mut counts = [[0u64; 3]; 3];
+    for &(opponent, column) in strategy.iter() {
+      counts[opponent.0][column] += 1;
+    }
+
+    let mut scores = Interpretation::all()
+      .into_iter()
+      .map(|interpretation| {
+        let mut total = 0;
+        for them in game.shapes() {
+          for (column, count) in counts[them.0].iter().enumerate() {
+            let choice = interpretation.choose(them, column, &game);
+            total += count * game.score(choice, them);
+          }
+        }
+        (interpretation, total)
+      })
+      .collect::<Vec<_>>();
+    // stable, so ties keep the order of `Interpretation::all`
+    scores.sort_by_key(|(_, score)| std::cmp::Reverse(*score));
+
+    Self { game, scores }
+  }
+
+  pub fn best(&self) -> (Interpretation, u64) {
+    self.scores[0]
+  }
+
+  pub fn score(&self, interpretation: Interpretation) -> Option<u64> {
+    self
+      .scores
+      .iter()
+      .find(|(i, _)| *i == interpretation)
+      .map(|(_, score)| *score)
+  }
+
+  pub fn iter(&self) -> impl Iterator<Item = (Interpretation, u64)> + '_ {
+    self.scores.iter().copied()
+  }
+}
+
+impl fmt::Display for Interpretations {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let width = self.best().1.to_string().len().max("Score".len());
+    write!(f, "{:>width$}  X, Y, Z", "Score")?;
+    for (interpretation, score) in self.iter() {
+      let meaning = match interpretation {
+        Interpretation::Shapes(shapes) => shapes.map(|s| self.game.name(s)).join(", "),
+        Interpretation::Outcomes(outcomes) => outcomes.map(|o| o.to_string()).join(", "),
+      };
+      write!(f, "\n{score:>width$}  {meaning}")?;
+      if interpretation == Interpretation::PART1 {
+        write!(f, " (part 1)")?;
+      } else if interpretation == Interpretation::PART2 {
+        write!(f, " (part 2)")?;
+      }
+    }
+    Ok(())
+  }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Outcome {
+  Win,
+  Lose,
+  Draw,
+}
+
+impl Outcome {
+  /// The shape to choose against `them` for this outcome. There may be several, in which case
+  /// this is the nearest one in the circle.
+  pub fn choose(&self, them: Shape, game: &Game) -> Shape {
+    let n = game.names.len();
+    match self {
+      Outcome::Draw => them,
+      Outcome::Win => Shape((them.0 + 1) % n),
+      Outcome::Lose => Shape((them.0 + n - 1) % n),
+    }
+  }
+}
+
+/// A shape of some [`Game`], which it can only be used with.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Shape(usize);
+
+impl Shape {
+  pub fn value(&self, game: &Game) -> u64 {
+    game.scoring.values[self.0]
+  }
+
+  pub fn outcome(&self, other: Shape, game: &Game) -> Outcome {
+    let n = game.names.len();
+    match (self.0 + n - other.0) % n {
+      0 => Outcome::Draw,
+      d if d % 2 == 1 => Outcome::Win,
+      _ => Outcome::Lose,
+    }
+  }
+
+  /// Points for the outcome of playing against `other`.
+  pub fn play(&self, other: Shape, game: &Game) -> u64 {
+    match self.outcome(other, game) {
+      Outcome::Win => game.scoring.win,
+      Outcome::Draw => game.scoring.draw,
+      Outcome::Lose => game.scoring.loss,
+    }
+  }
+}
+
+impl fmt::Display for Outcome {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let outcome = match self {
+      Outcome::Win => "win",
+      Outcome::Lose => "lose",
+      Outcome::Draw => "draw",
+    };
+    write!(f, "{outcome}")
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  const EXAMPLE: &str = include_str!("example.txt");
+
+  #[test]
+  fn part1() {
+    assert_eq!(Day2::part1(&Day2::parse(EXAMPLE).unwrap()), 15);
+  }
+
+  #[test]
+  fn part2() {
+    assert_eq!(Day2::part2(&Day2::parse(EXAMPLE).unwrap()), 12);
+  }
+
+  #[test]
+  fn parse_errors() {
+    let error = Day2::parse("A Y\nD X").unwrap_err();
+    assert_eq!((error.line, error.column), (2, 1));
+    let error = Day2::parse("A Y\nB W").unwrap_err();
+    assert_eq!((error.line, error.column), (2, 3));
+  }
+
+  #[test]
+  fn interpretations() {
+    let strategy = Day2::parse(EXAMPLE).unwrap();
+    let interpretations = Interpretations::new(&strategy);
+    assert_eq!(interpretations.iter().count(), 12);
+    assert_eq!(interpretations.score(Interpretation::PART1), Some(15));
+    assert_eq!(interpretations.score(Interpretation::PART2), Some(12));
+    // always choosing the winning shape
+    assert_eq!(
+      interpretations.best(),
+      (Interpretation::Shapes([Shape(2), Shape(1), Shape(0)]), 24)
+    );
+    let report = interpretations.to_string();
+    assert_eq!(
+      report.lines().take(4).collect::<Vec<_>>(),
+      [
+        "Score  X, Y, Z",
+        "   24  Scissors, Paper, Rock",
+        "   18  win, lose, draw",
+        "   15  Rock, Paper, Scissors (part 1)",
+      ]
+    );
+    assert!(report.ends_with("   12  lose, draw, win (part 2)\n    6  Rock, Scissors, Paper"));
+  }
+
+  #[test]
+  fn interpretations_of_typed_rounds() {
+    // always `Y` against Paper: Paper draws, Scissors wins and Rock loses
+    let strategy = [(Shape(1), 1); 4];
+    let interpretations = Interpretations::new(&strategy);
+    assert_eq!(interpretations.score(Interpretation::PART1), Some(20));
+    assert_eq!(interpretations.score(Interpretation::PART2), Some(20));
+    assert_eq!(interpretations.best().1, 36);
+  }
+
+  #[test]
+  fn tournament() {
+    let game = Game::rock_paper_scissors_spock_lizard();
+    let shape = |name| game.shape(name).unwrap();
+    for (winner, loser) in [
+      ("Scissors", "Paper"),
+      ("Paper", "Rock"),
+      ("Rock", "Lizard"),
+      ("Lizard", "Spock"),
+      ("Spock", "Scissors"),
+      ("Scissors", "Lizard"),
+      ("Lizard", "Paper"),
+      ("Paper", "Spock"),
+      ("Spock", "Rock"),
+      ("Rock", "Scissors"),
+    ] {
+      assert_eq!(shape(winner).outcome(shape(loser), &game), Outcome::Win);
+      assert_eq!(shape(loser).outcome(shape(winner), &game), Outcome::Lose);
+    }
+
+    // every shape beats exactly half of the others, and `choose` agrees with `outcome`
+    for n in [3, 5, 7, 9] {
+      let game = Game::new((0..n).map(|i| i.to_string()), Scoring::standard(n)).unwrap();
+      for a in game.shapes() {
+        let wins = game
+          .shapes()
+          .filter(|b| a.outcome(*b, &game) == Outcome::Win);
+        assert_eq!(wins.count(), n / 2);
+        for outcome in [Outcome::Win, Outcome::Lose, Outcome::Draw] {
+          assert_eq!(outcome.choose(a, &game).outcome(a, &game), outcome);
+        }
+      }
+    }
+  }
+
+  #[test]
+  fn scoring() {
+    let scoring = Scoring {
+      values: vec![10, 20, 30],
+      win: 100,
+      draw: 50,
+      loss: 1,
+    };
+    let game = Game::new(["R", "P", "S"], scoring.clone()).unwrap();
+    let shape = |name| game.shape(name).unwrap();
+    assert_eq!(game.score(shape("P"), shape("R")), 120);
+    assert_eq!(game.score(shape("S"), shape("S")), 80);
+    assert_eq!(game.score(shape("R"), shape("P")), 11);
+
+    assert_eq!(
+      Game::new(["R", "P", "S", "L"], Scoring::standard(4)).unwrap_err(),
+      GameError::ShapeCount(4)
+    );
+    assert_eq!(
+      Game::new(["R", "P", "S", "L", "K"], scoring).unwrap_err(),
+      GameError::ValueCount {
+        expected: 5,
+        found: 3
+      }
+    );
+  }
+}
@@ -0,0 +1,166 @@
+use std::mem::MaybeUninit;
+
+use itertools::Itertools;
+
+use crate::gen::Rng;
+use crate::{ParseError, Solution};
+
+pub struct Day3;
+
+impl Solution for Day3 {
+  type Input<'a> = Vec<&'a str>;
+  type Output1 = u64;
+  type Output2 = u64;
+
+  fn parse(source: &str) -> Result<Self::Input<'_>, ParseError> {
+    let rucksacks: Vec<_> = source.trim().split('\n').collect();
+
+    // the parts rely on these to find a common item without checking
+    for rucksack in rucksacks.iter() {
+      if let Some(i) = rucksack.find(|c: char| !c.is_ascii_alphabetic()) {
+        let item = &rucksack[i..i + rucksack[i..].chars().next().unwrap().len_utf8()];
+        return Err(ParseError::new(
+          source,
+          item,
+          "an item type `a`-`z` or `A`-`Z`",
+        ));
+      }
+      if rucksack.is_empty() || rucksack.len() % 2 != 0 {
+        return Err(ParseError::new(
+          source,
+          rucksack,
+          "two equally sized compartments",
+        ));
+      }
+      let (first, second) = rucksack.split_at(rucksack.len() / 2);
+      if !first.chars().any(|item| second.contains(item)) {
+        return Err(ParseError::new(
+          source,
+          rucksack,
+          "an item type in both compartments",
+        ));
+      }
+    }
+    if rucksacks.len() % 3 != 0 {
+      let last = rucksacks.last().unwrap();
+      return Err(ParseError::after(source, last, "groups of three rucksacks"));
+    }
+    for group in rucksacks.chunks(3) {
+      let [a, b, c] = group else { unreachable!() };
+      if !a.chars().any(|item| b.contains(item) && c.contains(item)) {
+        return Err(ParseError::new(
+          source,
+          a,
+          "an item type shared by the group of three",
+        ));
+      }
+    }
+
+    Ok(rucksacks)
+  }
+
+  fn part1(rucksacks: &Self::Input<'_>) -> Self::Output1 {
+    let mut total_priority = 0;
+    for rucksack in rucksacks.iter() {
+      let (first, second) = rucksack.split_at(rucksack.len() / 2);
+
+      let mut common = MaybeUninit::<char>::uninit();
+      for item in first.chars() {
+        if second.contains(item) {
+          common.write(item);
+          break;
+        }
+      }
+      // input always contains exactly one common item type in both compartments
+      let common = unsafe { common.assume_init() };
+
+      total_priority += priority(common);
+    }
+    total_priority
+  }
+
+  fn part2(rucksacks: &Self::Input<'_>) -> Self::Output2 {
+    let mut total_priority = 0;
+    for group in rucksacks.iter().chunks(3).into_iter() {
+      let mut parts = take::<3, _>(group).unwrap();
+      parts.sort_by_key(|v| usize::MAX - v.len());
+
+      let mut common = MaybeUninit::<char>::uninit();
+      let [a, b, c] = parts;
+      for item in a.chars() {
+        if b.contains(item) && c.contains(item) {
+          common.write(item);
+          break;
+        }
+      }
+      // input always contains exactly one common item type in all three compartments
+      let common = unsafe { common.assume_init() };
+
+      total_priority += priority(common);
+    }
+    total_priority
+  }
+
+  /// `size` groups of three rucksacks.
+  fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+    let mut rucksacks = vec![];
+    for _ in 0..size.max(1) {
+      let mut items = ('a'..='z').chain('A'..='Z').collect::<Vec<_>>();
+      rng.shuffle(&mut items);
+      let (badge, items) = items.split_first().unwrap();
+      // each elf packs from its own 17 item types, so that only the badge is shared by the group
+      for items in items.chunks(17) {
+        let (shared, items) = items.split_first().unwrap();
+        let (first, second) = items.split_at(8);
+        let len = rng.range(2..=16) as usize;
+        let mut a = vec![*badge, *shared];
+        a.extend((2..len).map(|_| *rng.pick(first)));
+        let mut b = vec![*shared];
+        b.extend((1..len).map(|_| *rng.pick(second)));
+        rng.shuffle(&mut a);
+        rng.shuffle(&mut b);
+        rucksacks.push(a.into_iter().chain(b).collect::<String>());
+      }
+    }
+    Some(rucksacks.join("\n"))
+  }
+}
+
+fn priority(item: char) -> u64 {
+  match item {
+    // a..z -> 1..26
+    'a'..='z' => (item as u8 - b'a' + 1) as u64,
+    // A..Z -> 27..52
+    'A'..='Z' => (item as u8 - b'A' + 27) as u64,
+    _ => panic!("invalid item type: {item}"),
+  }
+}
+
+fn take<const N: usize, I: Iterator>(mut iter: I) -> Option<[I::Item; N]> {
+  let mut uninit: [MaybeUninit<I::Item>; N] = unsafe { MaybeUninit::uninit().assume_init() };
+
+  for elem in &mut uninit {
+    unsafe { std::ptr::write(elem.as_mut_ptr(), iter.next()?) };
+  }
+
+  let array = unsafe { std::ptr::read(&uninit as *const _ as *const [I::Item; N]) };
+
+  Some(array)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  const EXAMPLE: &str = include_str!("example.txt");
+
+  #[test]
+  fn part1() {
+    assert_eq!(Day3::part1(&Day3::parse(EXAMPLE).unwrap()), 157);
+  }
+
+  #[test]
+  fn part2() {
+    assert_eq!(Day3::part2(&Day3::parse(EXAMPLE).unwrap()), 70);
+  }
+}
@@ -0,0 +1,93 @@
+use crate::gen::Rng;
+use crate::parse::Text;
+use crate::{ParseError, Solution};
+
+pub struct Day4;
+
+impl Solution for Day4 {
+  type Input<'a> = Vec<(Span, Span)>;
+  type Output1 = usize;
+  type Output2 = usize;
+
+  fn parse(source: &str) -> Result<Self::Input<'_>, ParseError> {
+    Text::new(source)
+      .trim()
+      .lines()
+      .map(|pair| {
+        let (a, b) = pair.split_once(",")?;
+        Ok((Span::parse(a)?, Span::parse(b)?))
+      })
+      .collect()
+  }
+
+  fn part1(pairs: &Self::Input<'_>) -> Self::Output1 {
+    let mut matches = 0;
+    for (a, b) in pairs.iter() {
+      if a.contains(*b) || b.contains(*a) {
+        matches += 1;
+      }
+    }
+    matches
+  }
+
+  fn part2(pairs: &Self::Input<'_>) -> Self::Output2 {
+    let mut matches = 0;
+    for (a, b) in pairs.iter() {
+      if a.overlap(*b) {
+        matches += 1;
+      }
+    }
+    matches
+  }
+
+  /// `size` pairs of sections between 1 and 99.
+  fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+    let mut span = || {
+      let start = rng.range(1..=99);
+      format!("{start}-{}", rng.range(start..=99))
+    };
+    let pairs = (0..size.max(1)).map(|_| format!("{},{}", span(), span()));
+    Some(pairs.collect::<Vec<_>>().join("\n"))
+  }
+}
+
+#[derive(Clone, Copy)]
+pub struct Span {
+  start: usize,
+  end: usize,
+}
+
+impl Span {
+  fn parse(text: Text) -> Result<Self, ParseError> {
+    let (start, end) = text.split_once("-")?;
+    Ok(Span {
+      start: start.parse("a section id")?,
+      end: end.parse("a section id")?,
+    })
+  }
+
+  fn contains(&self, other: Span) -> bool {
+    self.start <= other.start && other.end <= self.end
+  }
+
+  fn overlap(&self, other: Span) -> bool {
+    self.start <= other.end && self.end >= other.start
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  const EXAMPLE: &str = include_str!("example.txt");
+
+  #[test]
+  fn part1() {
+    assert_eq!(Day4::part1(&Day4::parse(EXAMPLE).unwrap()), 2);
+  }
+
+  #[test]
+  fn part2() {
+    assert_eq!(Day4::part2(&Day4::parse(EXAMPLE).unwrap()), 4);
+  }
+}
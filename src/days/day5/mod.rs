@@ -0,0 +1,478 @@
+use std::fmt;
+use std::str::FromStr;
+
+use logos::Logos;
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+use crate::cursor::{self, Cursor};
+use crate::error::parse_as;
+use crate::gen::Rng;
+use crate::step::Stepper;
+use crate::{ParseError, Solution};
+
+pub struct Day5;
+
+impl Solution for Day5 {
+  type Input<'a> = (Cargo<'a>, Vec<Instruction>);
+  type Output1 = String;
+  type Output2 = String;
+
+  fn parse(source: &str) -> Result<Self::Input<'_>, ParseError> {
+    // only trim the end, leading spaces are part of the cargo drawing
+    let input = source.trim_end();
+
+    let (cargo, instructions) = input
+      .split_once("\n\n")
+      .ok_or_else(|| ParseError::after(source, input, "a blank line before the instructions"))?;
+
+    // the last line numbers the stacks, which may be empty
+    let (crates, numbers) = cargo.rsplit_once('\n').unwrap_or(("", cargo));
+    let mut count = 0;
+    for n in numbers.split_whitespace() {
+      count += 1;
+      if n != count.to_string() {
+        return Err(ParseError::new(source, n, format!("stack number {count}")));
+      }
+    }
+    if count == 0 {
+      return Err(ParseError::new(source, numbers, "a line of stack numbers"));
+    }
+
+    let mut builder = CargoBuilder::default();
+    for line in crates.split('\n').filter(|l| !l.is_empty()) {
+      let error = |e: cursor::Error<Token>| e.into_parse_error(source, line);
+      let mut cursor = Cursor::new(line).map_err(error)?;
+      let mut index = 0;
+      while !cursor.is_eof() {
+        if index >= count {
+          let expected = format!("at most {count} stacks");
+          return Err(ParseError::new(source, cursor.slice(), expected));
+        }
+        if let Token::Crate(label) = cursor.current().kind {
+          builder.push_crate(index, label);
+        }
+        index += 1;
+        cursor.bump().map_err(error)?;
+      }
+    }
+    let cargo = builder.finish(count);
+
+    // both cranes move the same number of crates, so the heights are the same for both parts
+    let mut heights = cargo.stacks.iter().map(Vec::len).collect::<Vec<_>>();
+    let instructions = instructions
+      .split('\n')
+      .map(|line| {
+        let inst = line
+          .parse::<Instruction>()
+          .map_err(|e| e.within(source, line))?;
+        if inst.from >= cargo.stacks.len() || inst.to >= cargo.stacks.len() {
+          let expected = format!("stacks between 1 and {}", cargo.stacks.len());
+          return Err(ParseError::new(source, line, expected));
+        }
+        if inst.from == inst.to {
+          return Err(ParseError::new(source, line, "two different stacks"));
+        }
+        if inst.quantity > heights[inst.from] {
+          let expected = format!(
+            "a move of at most the {} crate(s) on stack {}",
+            heights[inst.from],
+            inst.from + 1
+          );
+          return Err(ParseError::new(source, line, expected));
+        }
+        heights[inst.from] -= inst.quantity;
+        heights[inst.to] += inst.quantity;
+        Ok(inst)
+      })
+      .collect::<Result<Vec<_>, _>>()?;
+
+    Ok((cargo, instructions))
+  }
+
+  fn part1((cargo, instructions): &Self::Input<'_>) -> Self::Output1 {
+    let mut cargo = cargo.clone();
+    for inst in instructions.iter() {
+      cargo.execute(*inst, Version::_9000);
+    }
+    cargo.top()
+  }
+
+  fn part2((cargo, instructions): &Self::Input<'_>) -> Self::Output2 {
+    let mut cargo = cargo.clone();
+    for inst in instructions.iter() {
+      cargo.execute(*inst, Version::_9001);
+    }
+    cargo.top()
+  }
+
+  /// Nine stacks of up to 20 crates, and `size` instructions which never take more crates than
+  /// a stack has.
+  fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+    let stacks = (0..9)
+      .map(|_| (0..rng.range(1..=20)).map(|_| *rng.pick(&LABELS)).collect())
+      .collect();
+    let initial = Cargo { stacks };
+
+    // both cranes leave the same number of crates on each stack
+    let mut cargo = initial.clone();
+    let mut instructions = vec![];
+    for _ in 0..size.max(1) {
+      let from = loop {
+        let from = rng.index(cargo.stacks.len());
+        if !cargo.stacks[from].is_empty() {
+          break from;
+        }
+      };
+      let to = (from + rng.range(1..=8) as usize) % cargo.stacks.len();
+      let quantity = rng.range(1..=cargo.stacks[from].len() as u64) as usize;
+      let inst = Instruction { quantity, from, to };
+      cargo.execute(inst, Version::_9000);
+      instructions.push(inst.to_string());
+    }
+
+    Some(format!("{initial}\n\n{}", instructions.join("\n")))
+  }
+}
+
+/// Every crate label, for generating cargo.
+const LABELS: [&str; 26] = [
+  "A", "B", "C", "D", "E", "F", "G", "H", "I", "J", "K", "L", "M", "N", "O", "P", "Q", "R", "S",
+  "T", "U", "V", "W", "X", "Y", "Z",
+];
+
+#[derive(Debug, Default)]
+struct CargoBuilder<'a> {
+  stacks: Vec<Vec<&'a str>>,
+}
+
+impl<'a> CargoBuilder<'a> {
+  fn push_crate(&mut self, index: usize, label: &'a str) {
+    if index >= self.stacks.len() {
+      for _ in 0..index - self.stacks.len() + 1 {
+        self.stacks.push(vec![]);
+      }
+    }
+
+    self.stacks[index].push(label);
+  }
+
+  fn finish(mut self, count: usize) -> Cargo<'a> {
+    self.stacks.resize(count, vec![]);
+    for stack in self.stacks.iter_mut() {
+      stack.reverse();
+    }
+    Cargo {
+      stacks: self.stacks,
+    }
+  }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Cargo<'a> {
+  stacks: Vec<Vec<&'a str>>,
+}
+
+#[derive(Clone, Copy)]
+enum Version {
+  _9000,
+  _9001,
+}
+
+impl<'a> Cargo<'a> {
+  fn execute(&mut self, inst: Instruction, version: Version) {
+    let Instruction { quantity, from, to } = inst;
+
+    let mut iter = self.stacks.iter_mut();
+    let (src, dest) = match from.cmp(&to) {
+      std::cmp::Ordering::Less => {
+        let src = iter.nth(from).unwrap();
+        let dest = iter.nth(to - from - 1).unwrap();
+        (src, dest)
+      }
+      std::cmp::Ordering::Greater => {
+        let dest = iter.nth(to).unwrap();
+        let src = iter.nth(from - to - 1).unwrap();
+        (src, dest)
+      }
+      std::cmp::Ordering::Equal => panic!(
+        "cannot move {} elements from stack {} to itself",
+        quantity, from
+      ),
+    };
+
+    match version {
+      Version::_9000 => dest.extend(src.drain(src.len() - quantity..src.len()).rev()),
+      Version::_9001 => dest.extend(src.drain(src.len() - quantity..src.len())),
+    }
+  }
+
+  fn top(&self) -> String {
+    let mut result = String::new();
+    for top in self.stacks.iter().filter_map(|stack| stack.last()) {
+      result += top;
+    }
+    result
+  }
+}
+
+/// Steps through the instructions one at a time, with the CrateMover 9000 for part 1 and the
+/// 9001 for part 2.
+pub fn stepper<'a>(
+  (cargo, instructions): &(Cargo<'a>, Vec<Instruction>),
+  part: u8,
+) -> Box<dyn Stepper + 'a> {
+  Box::new(Crane {
+    cargo: cargo.clone(),
+    instructions: instructions.clone(),
+    next: 0,
+    version: match part {
+      1 => Version::_9000,
+      _ => Version::_9001,
+    },
+  })
+}
+
+struct Crane<'a> {
+  cargo: Cargo<'a>,
+  instructions: Vec<Instruction>,
+  next: usize,
+  version: Version,
+}
+
+impl Stepper for Crane<'_> {
+  fn step(&mut self) -> bool {
+    let Some(inst) = self.instructions.get(self.next) else {
+      return false;
+    };
+    self.cargo.execute(*inst, self.version);
+    self.next += 1;
+    true
+  }
+
+  fn vars(&self) -> Vec<(String, i64)> {
+    let mut vars = vec![("instruction".to_owned(), self.next as i64)];
+    for (i, stack) in self.cargo.stacks.iter().enumerate() {
+      vars.push((format!("stack{}", i + 1), stack.len() as i64));
+    }
+    vars
+  }
+}
+
+impl fmt::Display for Crane<'_> {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    writeln!(f, "{}", self.cargo)?;
+    match self.instructions.get(self.next) {
+      Some(inst) => write!(f, "next: {inst}"),
+      None => write!(f, "top: {}", self.cargo.top()),
+    }
+  }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Instruction {
+  quantity: usize,
+  from: usize,
+  to: usize,
+}
+
+impl FromStr for Instruction {
+  type Err = ParseError;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    static REGEX: Lazy<Regex> =
+      Lazy::new(|| Regex::new(r"^move (\d+) from (\d+) to (\d+)$").unwrap());
+
+    let cap = REGEX
+      .captures(s)
+      .ok_or_else(|| ParseError::new(s, s, "`move <n> from <stack> to <stack>`"))?;
+    let stack = |i: usize| {
+      let m = cap.get(i).unwrap().as_str();
+      match parse_as::<usize>(s, m, "a stack number")? {
+        // crate indices use 1-indexing
+        0 => Err(ParseError::new(s, m, "a stack number starting at 1")),
+        n => Ok(n - 1),
+      }
+    };
+
+    Ok(Instruction {
+      quantity: parse_as(s, &cap[1], "a quantity")?,
+      from: stack(2)?,
+      to: stack(3)?,
+    })
+  }
+}
+
+impl fmt::Display for Instruction {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(
+      f,
+      "move {} from {} to {}",
+      self.quantity,
+      self.from + 1,
+      self.to + 1
+    )
+  }
+}
+
+/// Draws the stacks the same way as the puzzle input, followed by a line of stack numbers.
+impl<'a> fmt::Display for Cargo<'a> {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let height = self.stacks.iter().map(Vec::len).max().unwrap_or(0);
+    for y in (0..height).rev() {
+      let row = self
+        .stacks
+        .iter()
+        .map(|stack| match stack.get(y) {
+          Some(label) => format!("[{label}]"),
+          None => "   ".to_owned(),
+        })
+        .collect::<Vec<_>>();
+      writeln!(f, "{}", row.join(" "))?;
+    }
+
+    let numbers = (1..=self.stacks.len())
+      .map(|n| format!(" {n} "))
+      .collect::<Vec<_>>();
+    write!(f, "{}", numbers.join(" "))
+  }
+}
+
+#[derive(Clone, Copy, Debug, Logos)]
+enum Token<'a> {
+  #[regex(r"\[\w\]", lex_crate)]
+  Crate(&'a str),
+  #[token(r"   ", lex_empty)]
+  Empty,
+  Eof,
+  #[error]
+  Error,
+}
+
+impl<'a> cursor::TokenKind<'a> for Token<'a> {
+  const EOF: Self = Token::Eof;
+  const EXPECTED: &'static str = "a crate like `[A]` or an empty slot";
+}
+
+impl fmt::Display for Token<'_> {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Token::Crate(label) => write!(f, "[{label}]"),
+      Token::Empty => write!(f, "{{empty}}"),
+      Token::Eof => write!(f, "{{eof}}"),
+      Token::Error => write!(f, "{{invalid}}"),
+    }
+  }
+}
+
+type Lexer<'a> = logos::Lexer<'a, Token<'a>>;
+
+fn lex_crate<'a>(lexer: &mut Lexer<'a>) -> &'a str {
+  let lexeme = lexer.slice();
+  let lexeme = &lexeme[1..lexeme.len() - 1];
+  // skip the separator between slots
+  if lexer.remainder().starts_with(' ') {
+    lexer.bump(1);
+  }
+  lexeme
+}
+
+fn lex_empty(lexer: &mut Lexer<'_>) {
+  // skip the separator between slots
+  if lexer.remainder().starts_with(' ') {
+    lexer.bump(1);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use proptest::collection::vec;
+  use proptest::prelude::*;
+
+  use super::*;
+  use crate::testing::mutated;
+
+  const EXAMPLE: &str = include_str!("example.txt");
+
+  #[test]
+  fn part1() {
+    assert_eq!(Day5::part1(&Day5::parse(EXAMPLE).unwrap()), "CMZ");
+  }
+
+  #[test]
+  fn part2() {
+    assert_eq!(Day5::part2(&Day5::parse(EXAMPLE).unwrap()), "MCD");
+  }
+
+  #[test]
+  fn steps() {
+    let input = Day5::parse(EXAMPLE).unwrap();
+    let mut crane = stepper(&input, 2);
+    assert!(crane.step());
+    assert!(crane.to_string().ends_with("next: move 3 from 1 to 3"));
+    while crane.step() {}
+    assert!(crane.to_string().ends_with("top: MCD"));
+    assert_eq!(crane.vars()[0], ("instruction".to_owned(), 4));
+  }
+
+  #[test]
+  fn too_many_crates() {
+    let input = format!("{}\nmove 5 from 2 to 1", EXAMPLE.trim_end());
+    let e = Day5::parse(&input).unwrap_err();
+    assert_eq!(e.line, 10);
+    assert!(e
+      .to_string()
+      .contains("a move of at most the 1 crate(s) on stack 2"));
+
+    // the example moves crates on and off stack 3, and leaves it with 4
+    let input = format!("{}\nmove 4 from 3 to 1", EXAMPLE.trim_end());
+    assert!(Day5::parse(&input).is_ok());
+    let input = format!("{}\nmove 5 from 3 to 1", EXAMPLE.trim_end());
+    assert!(Day5::parse(&input).is_err());
+  }
+
+  fn input() -> impl Strategy<Value = (Cargo<'static>, Vec<Instruction>)> {
+    let stack = vec(proptest::sample::select(&LABELS[..]), 0..6);
+    vec(stack, 2..10)
+      .prop_flat_map(|stacks| {
+        let n = stacks.len();
+        let inst = (1..20usize, 0..n, 1..n).prop_map(move |(quantity, from, offset)| Instruction {
+          quantity,
+          from,
+          to: (from + offset) % n,
+        });
+        (Just(Cargo { stacks }), vec(inst, 1..10))
+      })
+      .prop_map(|(cargo, mut instructions)| {
+        // never take more crates than a stack has
+        let mut heights = cargo.stacks.iter().map(Vec::len).collect::<Vec<_>>();
+        for inst in instructions.iter_mut() {
+          inst.quantity %= heights[inst.from] + 1;
+          heights[inst.from] -= inst.quantity;
+          heights[inst.to] += inst.quantity;
+        }
+        (cargo, instructions)
+      })
+  }
+
+  fn render((cargo, instructions): &(Cargo<'_>, Vec<Instruction>)) -> String {
+    let instructions = instructions.iter().map(Instruction::to_string);
+    format!("{cargo}\n\n{}", instructions.collect::<Vec<_>>().join("\n"))
+  }
+
+  proptest! {
+    #[test]
+    fn input_round_trips(input in input()) {
+      let rendered = render(&input);
+      prop_assert_eq!(Day5::parse(&rendered).unwrap(), input);
+    }
+
+    #[test]
+    fn mutated_input_fails_or_round_trips(input in mutated(EXAMPLE)) {
+      if let Ok(parsed) = Day5::parse(&input) {
+        let rendered = render(&parsed);
+        prop_assert_eq!(Day5::parse(&rendered).unwrap(), parsed);
+      }
+    }
+  }
+}
@@ -0,0 +1,129 @@
+use crate::gen::Rng;
+use crate::{ParseError, Solution};
+
+pub struct Day6;
+
+impl Solution for Day6 {
+  type Input<'a> = &'a str;
+  type Output1 = usize;
+  type Output2 = usize;
+
+  fn parse(source: &str) -> Result<Self::Input<'_>, ParseError> {
+    let input = source.trim();
+    // `Window` slices by byte
+    if let Some(i) = input.find(|c: char| !c.is_ascii_lowercase()) {
+      let c = input[i..].chars().next().unwrap();
+      return Err(ParseError::new(
+        source,
+        &input[i..i + c.len_utf8()],
+        "a letter `a`-`z`",
+      ));
+    }
+    // a start-of-message marker always contains a start-of-packet marker
+    if find_marker(input, 14).is_none() {
+      return Err(ParseError::after(
+        source,
+        input,
+        "a marker of 14 different characters",
+      ));
+    }
+    Ok(input)
+  }
+
+  fn part1(input: &Self::Input<'_>) -> Self::Output1 {
+    find_marker(input, 4).expect("checked by `parse`")
+  }
+
+  fn part2(input: &Self::Input<'_>) -> Self::Output2 {
+    find_marker(input, 14).expect("checked by `parse`")
+  }
+
+  /// `size` characters which never contain four different ones in a row, followed by both
+  /// markers, so that the whole buffer has to be searched.
+  fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+    let mut buffer = (0..size)
+      .map(|_| *rng.pick(&['a', 'b', 'c']))
+      .collect::<String>();
+    let mut marker = ('d'..='z').collect::<Vec<_>>();
+    rng.shuffle(&mut marker);
+    buffer.extend(&marker[..14]);
+    Some(buffer)
+  }
+}
+
+fn find_marker(input: &str, n: usize) -> Option<usize> {
+  for (i, s) in Window::new(input, n).enumerate() {
+    if has_only_unique_chars(s) {
+      return Some(i + n);
+    }
+  }
+  None
+}
+
+struct Window<'a> {
+  s: &'a str,
+  n: usize,
+  i: usize,
+}
+
+impl<'a> Window<'a> {
+  fn new(s: &'a str, n: usize) -> Window<'a> {
+    Window { s, n, i: 0 }
+  }
+}
+
+impl<'a> Iterator for Window<'a> {
+  type Item = &'a str;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    let (s, len, i) = (self.s, self.n, self.i);
+
+    if s.len() - i < len {
+      return None;
+    }
+
+    let r = Some(&s[i..i + len]);
+    self.i += 1;
+    r
+  }
+}
+
+fn has_only_unique_chars(s: &str) -> bool {
+  if s.len() < 2 {
+    return true;
+  }
+
+  let (mut needle, mut remainder) = s.split_at(1);
+  while !remainder.is_empty() {
+    if remainder.contains(needle) {
+      return false;
+    }
+    (needle, remainder) = remainder.split_at(1);
+  }
+
+  true
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  const EXAMPLE: &str = include_str!("example.txt");
+
+  #[test]
+  fn part1() {
+    assert_eq!(Day6::part1(&Day6::parse(EXAMPLE).unwrap()), 5);
+  }
+
+  #[test]
+  fn part2() {
+    assert_eq!(Day6::part2(&Day6::parse(EXAMPLE).unwrap()), 23);
+  }
+
+  #[test]
+  fn no_marker() {
+    let e = Day6::parse("aaaaaaa").unwrap_err();
+    assert_eq!((e.line, e.column), (1, 8));
+    assert!(Day6::parse("abcdabcdabcdabcd").is_err());
+  }
+}
@@ -0,0 +1,599 @@
+use std::path::{Path, PathBuf};
+
+use vfs::PathClean;
+
+use crate::error::parse_as;
+use crate::gen::Rng;
+use crate::{ParseError, Solution};
+
+pub struct Day7;
+
+const DISK: usize = 70_000_000;
+const REQUIRED: usize = 30_000_000;
+
+impl Solution for Day7 {
+  type Input<'a> = vfs::Fs;
+  type Output1 = usize;
+  type Output2 = usize;
+
+  fn parse(source: &str) -> Result<Self::Input<'_>, ParseError> {
+    let fs = explored(source)?;
+
+    // part 2 needs something to delete, and the files have to fit on the disk
+    let total = fs.size("/").unwrap();
+    if !(DISK - REQUIRED..=DISK).contains(&total) {
+      return Err(ParseError::after(
+        source,
+        source.trim(),
+        format!(
+          "files using between {} and {DISK} bytes, found {total}",
+          DISK - REQUIRED
+        ),
+      ));
+    }
+
+    Ok(fs)
+  }
+
+  fn part1(fs: &Self::Input<'_>) -> Self::Output1 {
+    let mut sum = 0;
+    for entry in fs.entries() {
+      use vfs::Entry::*;
+
+      let Dir(_) = entry else {
+        continue;
+      };
+      let size = fs.size(entry.path()).unwrap();
+      if size > 100_000 {
+        continue;
+      }
+
+      sum += size;
+    }
+    sum
+  }
+
+  fn part2(fs: &Self::Input<'_>) -> Self::Output2 {
+    let total = fs.size("/").unwrap();
+    let unused = DISK - total;
+    let required = REQUIRED - unused;
+
+    let mut smallest = usize::MAX;
+    for entry in fs.entries() {
+      use vfs::Entry::*;
+
+      let Dir(_) = entry else {
+        continue;
+      };
+      let size = fs.size(entry.path()).unwrap();
+      if size >= required && size < smallest {
+        smallest = size;
+        continue;
+      }
+    }
+    smallest
+  }
+
+  /// `size` directories, where about half of them are nested in the one before, so that the tree
+  /// gets deep. File sizes add up to between 45M and 65M, so that part 2 has something to delete.
+  fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+    let dirs = size.max(1);
+    let mut children = vec![vec![]; dirs];
+    for dir in 1..dirs {
+      let parent = if rng.ratio(1, 2) {
+        dir - 1
+      } else {
+        rng.index(dir)
+      };
+      children[parent].push(dir);
+    }
+    let mut files = (0..dirs)
+      .map(|dir| {
+        let min = (dir == 0) as u64;
+        (0..rng.range(min..=4))
+          .map(|_| rng.range(1..=1000))
+          .collect::<Vec<_>>()
+      })
+      .collect::<Vec<_>>();
+    let weight = files.iter().flatten().sum::<u64>();
+    let total = rng.range(45_000_000..=65_000_000);
+    for size in files.iter_mut().flatten() {
+      *size = (*size * total / weight).max(1);
+    }
+
+    // walk the tree without recursion, since it may be very deep
+    enum Step {
+      Enter(usize),
+      Leave,
+    }
+    let mut lines = vec!["$ cd /".to_owned()];
+    let mut steps = vec![Step::Enter(0)];
+    while let Some(step) = steps.pop() {
+      let dir = match step {
+        Step::Enter(dir) => dir,
+        Step::Leave => {
+          lines.push("$ cd ..".to_owned());
+          continue;
+        }
+      };
+      if dir != 0 {
+        lines.push(format!("$ cd d{dir}"));
+      }
+      lines.push("$ ls".to_owned());
+      lines.extend(children[dir].iter().map(|child| format!("dir d{child}")));
+      lines.extend(
+        files[dir]
+          .iter()
+          .enumerate()
+          .map(|(i, size)| format!("{size} f{i}.txt")),
+      );
+      for &child in children[dir].iter().rev() {
+        steps.push(Step::Leave);
+        steps.push(Step::Enter(child));
+      }
+    }
+    Some(lines.join("\n"))
+  }
+}
+
+/// The filesystem explored by the terminal output in `source`.
+fn explored(source: &str) -> Result<vfs::Fs, ParseError> {
+  let input = source.trim();
+
+  let mut fs = vfs::Fs::new();
+  let mut cwd = PathBuf::from("/");
+
+  let mut lines = input.split('\n').peekable();
+  while let Some(line) = lines.next() {
+    let Some(command) = line.strip_prefix("$ ") else {
+      return Err(ParseError::new(
+        source,
+        line,
+        "a command starting with `$ `",
+      ));
+    };
+    match Command::parse(source, command)? {
+      Command::ChangeDir(path) => {
+        cwd = cwd.join(path).clean();
+      }
+      Command::List => {
+        while let Some(line) = lines.next_if(|line| !line.starts_with('$')) {
+          let (info, name) = line
+            .split_once(' ')
+            .ok_or_else(|| ParseError::new(source, line, "`dir <name>` or `<size> <name>`"))?;
+          let path = cwd.join(name).clean();
+          let result = match info {
+            "dir" => fs.create_dir(&path),
+            _ => fs.create_file(&path, parse_as(source, info, "a file size or `dir`")?),
+          };
+          result.map_err(|e| ParseError::new(source, name, format!("a valid entry, {e}")))?;
+        }
+      }
+    }
+  }
+
+  Ok(fs)
+}
+
+enum Command<'a> {
+  ChangeDir(&'a Path),
+  List,
+}
+
+impl<'a> Command<'a> {
+  fn parse(source: &str, s: &'a str) -> Result<Self, ParseError> {
+    use Command::*;
+    if let Some(path) = s.strip_prefix("cd ") {
+      Ok(ChangeDir(Path::new(path)))
+    } else if s == "ls" {
+      Ok(List)
+    } else {
+      Err(ParseError::new(source, s, "`cd <path>` or `ls`"))
+    }
+  }
+}
+
+pub mod vfs {
+  #![allow(dead_code)]
+
+  use std::collections::BTreeMap;
+  use std::fmt;
+  use std::path::{Component, Path, PathBuf};
+
+  use slotmap::SlotMap;
+  use thiserror::Error;
+
+  slotmap::new_key_type! {
+    pub struct EntryId;
+  }
+
+  pub struct Fs {
+    storage: SlotMap<EntryId, Entry>,
+    index: BTreeMap<PathBuf, EntryId>,
+    root: EntryId,
+  }
+
+  impl Default for Fs {
+    fn default() -> Self {
+      Self::new()
+    }
+  }
+
+  impl Fs {
+    pub fn new() -> Self {
+      let mut storage = SlotMap::with_key();
+      let root = storage.insert(Entry::dir("/"));
+      let index = BTreeMap::from([("/".into(), root)]);
+      Self {
+        storage,
+        root,
+        index,
+      }
+    }
+
+    pub fn root(&self) -> EntryId {
+      self.root
+    }
+
+    pub fn open(&self, path: impl AsRef<Path>) -> Result<&Entry> {
+      let path = path.as_ref();
+      self
+        .index
+        .get(path)
+        .and_then(|id| self.storage.get(*id))
+        .ok_or_else(|| Error::FileNotFound(path.into()))
+    }
+
+    pub fn open_mut(&mut self, path: impl AsRef<Path>) -> Result<&mut Entry> {
+      let path = path.as_ref();
+      self
+        .index
+        .get(path)
+        .and_then(|id| self.storage.get_mut(*id))
+        .ok_or_else(|| Error::FileNotFound(path.into()))
+    }
+
+    pub fn create_file(&mut self, path: impl AsRef<Path>, size: usize) -> Result<()> {
+      let path = path.as_ref();
+      self.create(path, Entry::file(path, size))
+    }
+
+    pub fn create_dir(&mut self, path: impl AsRef<Path>) -> Result<()> {
+      let path = path.as_ref();
+      self.create(path, Entry::dir(path))
+    }
+
+    fn create(&mut self, path: impl AsRef<Path>, entry: Entry) -> Result<()> {
+      let path = path.as_ref();
+
+      if self.open(path).is_ok() {
+        return Err(Error::FileExists(path.into()));
+      }
+
+      let Some(parent_path) = path.parent() else {
+        return Err(Error::ParentNotFound(path.into()));
+      };
+
+      let id = self.storage.insert(entry);
+
+      let Ok(parent) = self.open_mut(parent_path) else {
+        self.storage.remove(id);
+        return Err(Error::ParentNotFound(path.into()));
+      };
+      let Ok(parent) = parent.as_dir_mut() else {
+        self.storage.remove(id);
+        return Err(Error::NotDir(parent_path.into()));
+      };
+
+      parent.add(id);
+
+      self.index.insert(path.into(), id);
+
+      Ok(())
+    }
+
+    pub fn size(&self, path: impl AsRef<Path>) -> Result<usize> {
+      match self.open(path)? {
+        Entry::File(file) => Ok(file.size),
+        Entry::Dir(dir) => {
+          let mut sum = 0;
+          for entry in dir.entries.iter().map(|id| self.storage.get(*id).unwrap()) {
+            sum += self.size(entry.path())?;
+          }
+          Ok(sum)
+        }
+      }
+    }
+
+    pub fn entries(&self) -> impl Iterator<Item = &Entry> {
+      self.storage.iter().map(|(_, entry)| entry)
+    }
+  }
+
+  #[derive(Debug)]
+  pub enum Entry {
+    File(File),
+    Dir(Dir),
+  }
+
+  impl Entry {
+    pub fn path(&self) -> &Path {
+      match self {
+        Entry::File(entry) => entry.path(),
+        Entry::Dir(entry) => entry.path(),
+      }
+    }
+
+    fn file(path: impl Into<PathBuf>, size: usize) -> Entry {
+      Entry::File(File {
+        path: path.into(),
+        size,
+      })
+    }
+
+    fn dir(path: impl Into<PathBuf>) -> Entry {
+      Entry::Dir(Dir {
+        path: path.into(),
+        entries: vec![],
+      })
+    }
+
+    pub fn as_dir(&self) -> Result<&Dir> {
+      match self {
+        Entry::File(e) => Err(Error::NotDir(e.path().into())),
+        Entry::Dir(e) => Ok(e),
+      }
+    }
+
+    pub fn as_dir_mut(&mut self) -> Result<&mut Dir> {
+      match self {
+        Entry::File(e) => Err(Error::NotDir(e.path().into())),
+        Entry::Dir(e) => Ok(e),
+      }
+    }
+
+    pub fn as_file(&self) -> Result<&File> {
+      match self {
+        Entry::File(e) => Ok(e),
+        Entry::Dir(e) => Err(Error::NotFile(e.path().into())),
+      }
+    }
+
+    pub fn as_file_mut(&mut self) -> Result<&mut File> {
+      match self {
+        Entry::File(e) => Ok(e),
+        Entry::Dir(e) => Err(Error::NotFile(e.path().into())),
+      }
+    }
+  }
+
+  #[derive(Debug)]
+  pub struct File {
+    path: PathBuf,
+    size: usize,
+  }
+
+  impl File {
+    pub fn path(&self) -> &Path {
+      self.path.as_path()
+    }
+
+    pub fn size(&self) -> usize {
+      self.size
+    }
+  }
+
+  #[derive(Debug)]
+  pub struct Dir {
+    path: PathBuf,
+    entries: Vec<EntryId>,
+  }
+
+  impl Dir {
+    pub fn path(&self) -> &Path {
+      self.path.as_path()
+    }
+
+    pub fn add(&mut self, entry: EntryId) {
+      self.entries.push(entry);
+    }
+  }
+
+  #[derive(Debug, Error)]
+  pub enum Error {
+    #[error("`{0}` not found")]
+    FileNotFound(PathBuf),
+    #[error("`{0}` already exists")]
+    FileExists(PathBuf),
+    #[error("parent dir of `{0}` not found")]
+    ParentNotFound(PathBuf),
+    #[error("`{0}` is not a directory")]
+    NotDir(PathBuf),
+    #[error("`{0}` is not a file")]
+    NotFile(PathBuf),
+  }
+
+  impl Error {
+    pub fn path(&self) -> &Path {
+      match self {
+        Error::FileNotFound(path) => path,
+        Error::FileExists(path) => path,
+        Error::ParentNotFound(path) => path,
+        Error::NotDir(path) => path,
+        Error::NotFile(path) => path,
+      }
+    }
+  }
+
+  pub type Result<T> = std::result::Result<T, Error>;
+
+  /// The Clean trait implements a `clean` method.
+  pub trait PathClean {
+    fn clean(&self) -> PathBuf;
+  }
+
+  /// PathClean implemented for `Path`
+  impl PathClean for Path {
+    fn clean(&self) -> PathBuf {
+      clean(self)
+    }
+  }
+
+  pub fn clean<P>(path: P) -> PathBuf
+  where
+    P: AsRef<Path>,
+  {
+    let mut out = Vec::new();
+
+    for comp in path.as_ref().components() {
+      match comp {
+        Component::CurDir => (),
+        Component::ParentDir => match out.last() {
+          Some(Component::RootDir) => (),
+          Some(Component::Normal(_)) => {
+            out.pop();
+          }
+          None
+          | Some(Component::CurDir)
+          | Some(Component::ParentDir)
+          | Some(Component::Prefix(_)) => out.push(comp),
+        },
+        comp => out.push(comp),
+      }
+    }
+
+    if !out.is_empty() {
+      out.iter().collect()
+    } else {
+      PathBuf::from(".")
+    }
+  }
+
+  impl fmt::Display for Fs {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+      fn fmt_inner(fs: &Fs, dir: &Dir, depth: usize, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for entry in dir.entries.iter() {
+          let entry = fs.storage.get(*entry).ok_or(fmt::Error)?;
+          let name = entry
+            .path()
+            .components()
+            .next_back()
+            .ok_or(fmt::Error)?
+            .as_os_str()
+            .to_string_lossy();
+
+          writeln!(f, "{:>width$}|- {}", "", name, width = depth * 3)?;
+          if let Entry::Dir(dir) = entry {
+            fmt_inner(fs, dir, depth + 1, f)?;
+          }
+        }
+
+        Ok(())
+      }
+
+      let root = self
+        .storage
+        .get(self.root())
+        .ok_or(fmt::Error)?
+        .as_dir()
+        .map_err(|_| fmt::Error)?;
+
+      writeln!(f, "<root>")?;
+      fmt_inner(self, root, 0, f)?;
+
+      Ok(())
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use proptest::collection::vec;
+  use proptest::prelude::*;
+
+  use super::*;
+  use crate::testing::mutated;
+
+  const EXAMPLE: &str = include_str!("example.txt");
+
+  #[test]
+  fn part1() {
+    assert_eq!(Day7::part1(&Day7::parse(EXAMPLE).unwrap()), 95437);
+  }
+
+  #[test]
+  fn part2() {
+    assert_eq!(Day7::part2(&Day7::parse(EXAMPLE).unwrap()), 24933642);
+  }
+
+  #[test]
+  fn disk_usage() {
+    let error = Day7::parse("$ cd /\n$ ls\n70000001 a.txt").err().unwrap();
+    assert_eq!((error.line, error.column), (3, 15));
+    assert!(error.expected.ends_with("found 70000001"));
+    let error = Day7::parse("$ cd /\n$ ls\n39999999 a.txt").err().unwrap();
+    assert!(error.expected.ends_with("found 39999999"));
+    assert_eq!(
+      Day7::part2(&Day7::parse("$ cd /\n$ ls\n70000000 a.txt").unwrap()),
+      70000000
+    );
+  }
+
+  #[derive(Clone, Debug)]
+  enum Node {
+    File(usize),
+    Dir(Vec<Node>),
+  }
+
+  fn tree() -> impl Strategy<Value = Vec<Node>> {
+    let leaf = (0..1_000_000usize).prop_map(Node::File);
+    let node = leaf.prop_recursive(4, 64, 6, |inner| vec(inner, 0..6).prop_map(Node::Dir));
+    vec(node, 0..6)
+  }
+
+  /// Writes the terminal output of exploring `nodes` in `cwd`, and builds the same tree in `fs`.
+  fn explore(nodes: &[Node], cwd: &Path, transcript: &mut Vec<String>, fs: &mut vfs::Fs) {
+    transcript.push("$ ls".into());
+    for (i, node) in nodes.iter().enumerate() {
+      match node {
+        Node::File(size) => {
+          transcript.push(format!("{size} f{i}.txt"));
+          fs.create_file(cwd.join(format!("f{i}.txt")), *size)
+            .unwrap();
+        }
+        Node::Dir(_) => {
+          transcript.push(format!("dir d{i}"));
+          fs.create_dir(cwd.join(format!("d{i}"))).unwrap();
+        }
+      }
+    }
+    for (i, node) in nodes.iter().enumerate() {
+      if let Node::Dir(children) = node {
+        transcript.push(format!("$ cd d{i}"));
+        explore(children, &cwd.join(format!("d{i}")), transcript, fs);
+        transcript.push("$ cd ..".into());
+      }
+    }
+  }
+
+  proptest! {
+    #[test]
+    fn transcript_round_trips(nodes in tree()) {
+      let mut transcript = vec!["$ cd /".to_owned()];
+      let mut expected = vfs::Fs::new();
+      explore(&nodes, Path::new("/"), &mut transcript, &mut expected);
+
+      let fs = explored(&transcript.join("\n")).unwrap();
+      prop_assert_eq!(fs.to_string(), expected.to_string());
+      prop_assert_eq!(fs.size("/").unwrap(), expected.size("/").unwrap());
+    }
+
+    #[test]
+    fn mutated_input_fails_or_parses(input in mutated(EXAMPLE)) {
+      if let Ok(fs) = Day7::parse(&input) {
+        fs.size("/").unwrap();
+        let _ = fs.to_string();
+      }
+    }
+  }
+}
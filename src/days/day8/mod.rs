@@ -1,20 +1,26 @@
 use std::collections::HashSet;
-use std::fmt;
 
-fn main() {
-  let input = include_str!("input.txt").trim();
+use crate::gen::Rng;
+use crate::grid::Grid;
+use crate::{ParseError, Solution};
 
-  let rows = input.split('\n').count();
-  let cols = input.split('\n').next().unwrap().chars().count();
-  let mut grid = Grid::new(rows, cols);
+pub struct Day8;
 
-  {
-    for (y, row) in input.split('\n').enumerate() {
-      for (x, height) in row.chars().enumerate() {
-        grid.set(x, y, height.to_digit(10).unwrap() as u8);
-      }
-    }
+impl Solution for Day8 {
+  type Input<'a> = Grid<u8>;
+  type Output1 = usize;
+  type Output2 = usize;
+
+  fn parse(source: &str) -> Result<Self::Input<'_>, ParseError> {
+    let input = source.trim();
 
+    Grid::try_parse(input, "a tree height `0`-`9`", |height| {
+      height.to_digit(10).map(|h| h as u8)
+    })
+    .map_err(|e| e.within(source, input))
+  }
+
+  fn part1(grid: &Self::Input<'_>) -> Self::Output1 {
     let mut scratch = vec![];
     let mut visible = HashSet::new();
 
@@ -29,7 +35,7 @@ fn main() {
     // rows
     for y in 0..grid.rows() {
       // left <- right visible
-      scratch.extend(grid.row(y));
+      scratch.extend(grid.row(y).copied());
 
       let mut skip_to = 0;
       loop {
@@ -70,7 +76,7 @@ fn main() {
     // columns
     for x in (0..grid.cols()).rev() {
       // top <- bottom visible
-      scratch.extend(grid.col(x));
+      scratch.extend(grid.col(x).copied());
 
       let mut skip_to = 0;
       loop {
@@ -82,14 +88,14 @@ fn main() {
         }
         visible.insert((highest.x, highest.y));
 
-        if highest.y == grid.cols() - 1 {
+        if highest.y == grid.rows() - 1 {
           break;
         }
         skip_to = highest.y + 1;
       }
 
       // top -> bottom visible
-      let mut take_n = grid.cols();
+      let mut take_n = grid.rows();
       loop {
         let mut highest = Tree::default();
         for (y, height) in scratch.iter().cloned().enumerate().take(take_n).rev() {
@@ -108,10 +114,10 @@ fn main() {
       scratch.clear();
     }
 
-    println!("Day 8 part 1 answer: {}", visible.len());
+    visible.len()
   }
 
-  {
+  fn part2(grid: &Self::Input<'_>) -> Self::Output2 {
     let mut scratch = vec![];
     // for each tree, measure its viewing distance along
     // the horizontal and vertical axes: +x, -x, +y, -y
@@ -122,9 +128,9 @@ fn main() {
     let mut highest_score = 0;
     for y in 0..grid.rows() {
       for x in 0..grid.cols() {
-        let height = grid.get(x, y);
+        let height = grid[(x, y)];
 
-        scratch.extend(grid.row(y));
+        scratch.extend(grid.row(y).copied());
         // -x
         let px = scratch
           .iter()
@@ -157,7 +163,7 @@ fn main() {
           .unwrap_or(grid.cols() - (x + 1));
         scratch.clear();
 
-        scratch.extend(grid.col(x));
+        scratch.extend(grid.col(x).copied());
         // -y
         let py = scratch
           .iter()
@@ -196,7 +202,18 @@ fn main() {
       }
     }
 
-    println!("Day 8 part 2 answer: {}", highest_score);
+    highest_score
+  }
+
+  /// A forest of `size` by `size` trees.
+  fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+    let side = size.max(1);
+    let rows = (0..side).map(|_| {
+      (0..side)
+        .map(|_| char::from_digit(rng.range(0..=9) as u32, 10).unwrap())
+        .collect::<String>()
+    });
+    Some(rows.collect::<Vec<_>>().join("\n"))
   }
 }
 
@@ -207,111 +224,29 @@ struct Tree {
   height: u8,
 }
 
-struct Grid {
-  data: Vec<u8>,
-  cols: usize,
-}
-
-impl Grid {
-  fn new(rows: usize, cols: usize) -> Self {
-    Self {
-      data: vec![0; rows * cols],
-      cols,
-    }
-  }
-
-  fn set(&mut self, x: usize, y: usize, v: u8) {
-    self.data[y * self.cols + x] = v;
-  }
-
-  fn get(&self, x: usize, y: usize) -> u8 {
-    self.data[y * self.cols + x]
-  }
-
-  fn rows(&self) -> usize {
-    self.data.len() / self.cols
-  }
-
-  fn cols(&self) -> usize {
-    self.cols
-  }
-
-  fn row(&self, y: usize) -> Row {
-    Row {
-      grid: self,
-      y,
-      cx: 0,
-    }
-  }
-
-  fn col(&self, x: usize) -> Column {
-    Column {
-      grid: self,
-      x,
-      cy: 0,
-    }
-  }
-}
+#[cfg(test)]
+mod tests {
+  use super::*;
 
-impl fmt::Display for Grid {
-  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-    for y in 0..self.data.len() / self.cols {
-      for x in 0..self.cols {
-        write!(f, "{}", self.get(x, y))?;
-      }
-      writeln!(f)?;
-    }
+  const EXAMPLE: &str = include_str!("example.txt");
 
-    Ok(())
+  #[test]
+  fn part1() {
+    assert_eq!(Day8::part1(&Day8::parse(EXAMPLE).unwrap()), 21);
   }
-}
-
-struct Row<'a> {
-  grid: &'a Grid,
-  y: usize,
-  cx: usize,
-}
 
-impl<'a> Iterator for Row<'a> {
-  type Item = u8;
-
-  fn next(&mut self) -> Option<Self::Item> {
-    if self.cx >= self.grid.cols() {
-      return None;
-    }
-
-    let x = {
-      let temp = self.cx;
-      self.cx += 1;
-      temp
-    };
-    let y = self.y;
-
-    Some(self.grid.data[y * self.grid.cols() + x])
+  #[test]
+  fn part2() {
+    assert_eq!(Day8::part2(&Day8::parse(EXAMPLE).unwrap()), 8);
   }
-}
 
-struct Column<'a> {
-  grid: &'a Grid,
-  x: usize,
-  cy: usize,
-}
-
-impl<'a> Iterator for Column<'a> {
-  type Item = u8;
-
-  fn next(&mut self) -> Option<Self::Item> {
-    if self.cy >= self.grid.rows() {
-      return None;
+  #[test]
+  fn not_square() {
+    // the first three rows of the example, and its first three columns
+    for forest in ["30373\n25512\n65332", "303\n255\n653\n335\n353"] {
+      let grid = Day8::parse(forest).unwrap();
+      assert_eq!(Day8::part1(&grid), 14);
+      assert_eq!(Day8::part2(&grid), 2);
     }
-
-    let y = {
-      let temp = self.cy;
-      self.cy += 1;
-      temp
-    };
-    let x = self.x;
-
-    Some(self.grid.data[y * self.grid.cols() + x])
   }
 }
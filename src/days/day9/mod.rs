@@ -0,0 +1,301 @@
+use std::collections::HashSet;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::error::parse_as;
+use crate::gen::Rng;
+use crate::geom::{Bounds, Direction, Point};
+use crate::step::Stepper;
+use crate::viz::{Image, Pixel, Recorder, Rgb};
+use crate::{ParseError, Solution};
+
+type Position = Point<i64>;
+
+pub struct Day9;
+
+impl Solution for Day9 {
+  type Input<'a> = Vec<Move>;
+  type Output1 = usize;
+  type Output2 = usize;
+
+  fn parse(source: &str) -> Result<Self::Input<'_>, ParseError> {
+    let input = source.trim();
+
+    input
+      .split('\n')
+      .map(|m| Move::from_str(m).map_err(|e| e.within(source, m)))
+      .collect()
+  }
+
+  fn part1(moves: &Self::Input<'_>) -> Self::Output1 {
+    simulate(moves, 1).visited.len()
+  }
+
+  fn part2(moves: &Self::Input<'_>) -> Self::Output2 {
+    simulate(moves, 9).visited.len()
+  }
+
+  /// `size` moves of up to 20 steps each.
+  fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+    let moves = (0..size.max(1))
+      .map(|_| format!("{} {}", rng.pick(&["U", "D", "L", "R"]), rng.range(1..=20)));
+    Some(moves.collect::<Vec<_>>().join("\n"))
+  }
+}
+
+fn simulate(moves: &[Move], length: usize) -> World {
+  let mut world = World::new(length);
+
+  for move_ in moves.iter() {
+    world.simulate(*move_);
+  }
+
+  world
+}
+
+struct World {
+  head: Position,
+  knots: Vec<Position>,
+  bounds: Bounds<i64>,
+  visited: HashSet<Position>,
+}
+
+impl World {
+  fn new(length: usize) -> Self {
+    Self {
+      head: Position::default(),
+      knots: vec![Position::default(); length],
+      bounds: Bounds::new(Position::default()),
+      visited: [Position::default()].into_iter().collect(),
+    }
+  }
+
+  fn simulate(&mut self, move_: Move) {
+    for _ in 0..move_.count() {
+      self.step(move_.direction());
+    }
+  }
+
+  fn step(&mut self, dir: Direction) {
+    // move head
+    self.head = self.head.step(dir);
+
+    // move tail to catch up
+    let mut prev = &self.head;
+    for current in self.knots.iter_mut() {
+      apply_knot_constraint(prev, current);
+      prev = &*current;
+    }
+
+    // expand bounds
+    self.bounds.expand(self.head);
+    for knot in self.knots.iter() {
+      self.bounds.expand(*knot);
+    }
+
+    // record tail (final knot) position
+    if let Some(last) = self.knots.last() {
+      self.visited.insert(*last);
+    }
+  }
+}
+
+fn apply_knot_constraint(head: &Position, tail: &mut Position) {
+  if head.chebyshev(*tail) > 1 {
+    let d = *head - *tail;
+    *tail += Point::new(d.x.signum(), d.y.signum());
+  }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub enum Move {
+  Up(u64),
+  Down(u64),
+  Left(u64),
+  Right(u64),
+}
+
+impl Move {
+  fn direction(&self) -> Direction {
+    match self {
+      Move::Up(_) => Direction::Up,
+      Move::Down(_) => Direction::Down,
+      Move::Left(_) => Direction::Left,
+      Move::Right(_) => Direction::Right,
+    }
+  }
+
+  fn count(&self) -> u64 {
+    match self {
+      Move::Up(n) => *n,
+      Move::Down(n) => *n,
+      Move::Left(n) => *n,
+      Move::Right(n) => *n,
+    }
+  }
+}
+
+impl FromStr for Move {
+  type Err = ParseError;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    use Move::*;
+    let (dir, n) = s
+      .split_once(' ')
+      .ok_or_else(|| ParseError::after(s, s, "a space"))?;
+    let n = parse_as::<u64>(s, n, "a step count")?;
+    match dir {
+      "U" => Ok(Up(n)),
+      "D" => Ok(Down(n)),
+      "L" => Ok(Left(n)),
+      "R" => Ok(Right(n)),
+      _ => Err(ParseError::new(s, dir, "one of `U`, `D`, `L` or `R`")),
+    }
+  }
+}
+
+impl fmt::Display for Move {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let dir = match self {
+      Move::Up(_) => "U",
+      Move::Down(_) => "D",
+      Move::Left(_) => "L",
+      Move::Right(_) => "R",
+    };
+    write!(f, "{dir} {}", self.count())
+  }
+}
+
+impl World {
+  /// Draws the area within `bounds`, so that frames of an animation all have the same size.
+  fn image(&self, bounds: Bounds<i64>) -> Image {
+    let (cols, rows) = (bounds.width(), bounds.height());
+    Image::from_fn(cols as usize, rows as usize, |x, y| {
+      let pos = bounds.min + Point::new(x as i64, y as i64);
+      if self.head == pos {
+        Pixel::new('H', Rgb::RED)
+      } else if let Some(idx) = self.knots.iter().position(|&p| p == pos) {
+        // knots are 1-indexed
+        let glyph = char::from_digit((idx + 1) as u32 % 10, 10).unwrap();
+        Pixel::new(glyph, Rgb::ORANGE)
+      } else if Position::default() == pos {
+        Pixel::new('s', Rgb::BLUE)
+      } else if self.visited.contains(&pos) {
+        Pixel::new('#', Rgb::GRAY)
+      } else {
+        Pixel::new('.', Rgb::BLACK)
+      }
+    })
+  }
+}
+
+impl fmt::Display for World {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    writeln!(f, "{}", self.image(self.bounds))
+  }
+}
+
+/// Records a frame for every step of the head, using a rope of `length` knots behind the head.
+pub fn animate(moves: &[Move], length: usize, recorder: &mut Recorder) -> anyhow::Result<()> {
+  // the whole area the rope moves through, so that every frame has the same size
+  let bounds = simulate(moves, length).bounds;
+
+  let mut world = World::new(length);
+  recorder.record(|| world.image(bounds))?;
+  for move_ in moves.iter() {
+    for _ in 0..move_.count() {
+      world.step(move_.direction());
+      recorder.record(|| world.image(bounds))?;
+    }
+  }
+  recorder.write(&world.image(bounds))
+}
+
+/// Steps the head one cell at a time, with a rope of one knot for part 1 and nine for part 2.
+pub fn stepper(moves: &[Move], part: u8) -> Box<dyn Stepper + '_> {
+  let length = match part {
+    1 => 1,
+    _ => 9,
+  };
+  Box::new(Rope {
+    world: World::new(length),
+    moves,
+    next: 0,
+    taken: 0,
+  })
+}
+
+struct Rope<'a> {
+  world: World,
+  moves: &'a [Move],
+  next: usize,
+  /// Steps taken of the move at `next`.
+  taken: u64,
+}
+
+impl Stepper for Rope<'_> {
+  fn step(&mut self) -> bool {
+    // skip moves of zero steps
+    while let Some(move_) = self.moves.get(self.next) {
+      if self.taken < move_.count() {
+        self.world.step(move_.direction());
+        self.taken += 1;
+        return true;
+      }
+      self.next += 1;
+      self.taken = 0;
+    }
+    false
+  }
+
+  fn vars(&self) -> Vec<(String, i64)> {
+    let tail = self.world.knots.last().unwrap();
+    vec![
+      ("move".to_owned(), self.next as i64),
+      ("head_x".to_owned(), self.world.head.x),
+      ("head_y".to_owned(), self.world.head.y),
+      ("tail_x".to_owned(), tail.x),
+      ("tail_y".to_owned(), tail.y),
+      ("visited".to_owned(), self.world.visited.len() as i64),
+    ]
+  }
+}
+
+impl fmt::Display for Rope<'_> {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{}", self.world)?;
+    match self.moves.get(self.next) {
+      Some(move_) => write!(f, "move: {move_}, {} step(s) taken", self.taken),
+      None => write!(f, "done"),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  const EXAMPLE: &str = include_str!("example.txt");
+
+  #[test]
+  fn part1() {
+    assert_eq!(Day9::part1(&Day9::parse(EXAMPLE).unwrap()), 13);
+  }
+
+  #[test]
+  fn part2() {
+    assert_eq!(Day9::part2(&Day9::parse(EXAMPLE).unwrap()), 1);
+  }
+
+  #[test]
+  fn steps() {
+    let moves = Day9::parse(EXAMPLE).unwrap();
+    let mut rope = stepper(&moves, 1);
+    for _ in 0..5 {
+      assert!(rope.step());
+    }
+    assert!(rope.to_string().ends_with("move: U 4, 1 step(s) taken"));
+    while rope.step() {}
+    assert_eq!(rope.vars()[5], ("visited".to_owned(), 13));
+  }
+}
@@ -0,0 +1,218 @@
+use std::fmt;
+use std::ops::RangeInclusive;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use crate::gen::Rng;
+use crate::{bench, ParseError, Solution};
+
+pub mod day1;
+pub mod day10;
+pub mod day11;
+pub mod day12;
+pub mod day13;
+pub mod day13_bump;
+pub mod day14;
+pub mod day2;
+pub mod day3;
+pub mod day4;
+pub mod day5;
+pub mod day6;
+pub mod day7;
+pub mod day8;
+pub mod day9;
+
+pub static DAYS: &[Day] = &[
+  Day::new::<day1::Day1>(1, "day1"),
+  Day::new::<day2::Day2>(2, "day2"),
+  Day::new::<day3::Day3>(3, "day3"),
+  Day::new::<day4::Day4>(4, "day4"),
+  Day::new::<day5::Day5>(5, "day5"),
+  Day::new::<day6::Day6>(6, "day6"),
+  Day::new::<day7::Day7>(7, "day7"),
+  Day::new::<day8::Day8>(8, "day8"),
+  Day::new::<day9::Day9>(9, "day9"),
+  Day::new::<day10::Day10>(10, "day10"),
+  Day::new::<day11::Day11>(11, "day11"),
+  Day::new::<day12::Day12>(12, "day12"),
+  Day::new::<day13::Day13>(13, "day13"),
+  Day::new::<day13_bump::Day13Bump>(13, "day13-bump"),
+  Day::new::<day14::Day14>(14, "day14"),
+];
+
+/// A type-erased [`Solution`], so that every day can live in one table.
+pub struct Day {
+  pub number: u32,
+  pub name: &'static str,
+  parts: [SolveFn; 2],
+  benches: [BenchFn; 2],
+  generate: GenerateFn,
+}
+
+type SolveFn = fn(&str) -> Result<String, ParseError>;
+type BenchFn = fn(&str, &bench::Config) -> Result<bench::Report, ParseError>;
+type GenerateFn = fn(&mut Rng, usize) -> Option<String>;
+
+impl Day {
+  pub(crate) const fn new<S: Solution>(number: u32, name: &'static str) -> Self {
+    Self {
+      number,
+      name,
+      parts: [solve::<S, 1>, solve::<S, 2>],
+      benches: [bench::<S, 1>, bench::<S, 2>],
+      generate: S::generate,
+    }
+  }
+
+  /// Whether this is the main solution for its day, as opposed to an experiment like `day13-bump`.
+  pub fn is_default(&self) -> bool {
+    self.name.strip_prefix("day") == Some(&self.number.to_string())
+  }
+
+  pub fn default_input(&self) -> PathBuf {
+    crate::input::default_path(self.number)
+  }
+
+  pub fn default_answers(&self) -> PathBuf {
+    crate::answers::default_path(self.number)
+  }
+
+  /// Parses `input` and solves `part` (1 or 2), returning the formatted answer.
+  pub fn solve(&self, part: u8, input: &str) -> Result<String, ParseError> {
+    (self.parts[part as usize - 1])(input)
+  }
+
+  /// Like [`Day::solve`], but measures parsing and solving separately over many runs.
+  pub fn bench(
+    &self,
+    part: u8,
+    input: &str,
+    config: &bench::Config,
+  ) -> Result<bench::Report, ParseError> {
+    (self.benches[part as usize - 1])(input, config)
+  }
+
+  /// Generates an input of roughly `size` units from `seed`, see [`crate::gen`].
+  pub fn generate(&self, size: usize, seed: u64) -> Option<String> {
+    (self.generate)(&mut Rng::new(seed), size)
+  }
+}
+
+impl fmt::Display for Day {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    if self.is_default() {
+      write!(f, "Day {}", self.number)
+    } else {
+      write!(f, "Day {} ({})", self.number, self.name)
+    }
+  }
+}
+
+fn solve<S: Solution, const PART: u8>(input: &str) -> Result<String, ParseError> {
+  Ok(answer::<S, PART>(&S::parse(input)?))
+}
+
+fn answer<S: Solution, const PART: u8>(input: &S::Input<'_>) -> String {
+  match PART {
+    1 => S::part1(input).to_string(),
+    _ => S::part2(input).to_string(),
+  }
+}
+
+fn bench<S: Solution, const PART: u8>(
+  input: &str,
+  config: &bench::Config,
+) -> Result<bench::Report, ParseError> {
+  let (parse, parsed, parse_allocs) = bench::measure(config, || S::parse(input));
+  let input = parsed?;
+
+  // the answer is formatted outside of the measurement
+  let (solve, answer, solve_allocs) = match PART {
+    1 => {
+      let (stats, output, allocs) = bench::measure(config, || S::part1(&input));
+      (stats, output.to_string(), allocs)
+    }
+    _ => {
+      let (stats, output, allocs) = bench::measure(config, || S::part2(&input));
+      (stats, output.to_string(), allocs)
+    }
+  };
+
+  Ok(bench::Report {
+    answer,
+    parse,
+    solve,
+    parse_allocs,
+    solve_allocs,
+  })
+}
+
+/// Selects days from the command line: `7`, `5..9`, `5..=9` or a variant name like `13-bump`.
+#[derive(Clone, Debug)]
+pub enum Selector {
+  Days(RangeInclusive<u32>),
+  Name(String),
+}
+
+impl Selector {
+  pub fn matches(&self, day: &Day) -> bool {
+    match self {
+      Selector::Days(range) => day.is_default() && range.contains(&day.number),
+      Selector::Name(name) => day.name == name || day.name.strip_prefix("day") == Some(name),
+    }
+  }
+}
+
+impl FromStr for Selector {
+  type Err = String;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    let number = |n: &str| {
+      n.parse::<u32>()
+        .map_err(|_| format!("invalid day number `{n}`"))
+    };
+
+    if let Some((start, end)) = s.split_once("..=") {
+      Ok(Selector::Days(number(start)?..=number(end)?))
+    } else if let Some((start, end)) = s.split_once("..") {
+      Ok(Selector::Days(
+        number(start)?..=number(end)?.saturating_sub(1),
+      ))
+    } else if let Ok(n) = s.parse::<u32>() {
+      Ok(Selector::Days(n..=n))
+    } else if DAYS.iter().any(|day| Selector::Name(s.into()).matches(day)) {
+      Ok(Selector::Name(s.into()))
+    } else {
+      Err(format!("unknown day `{s}`"))
+    }
+  }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+  use super::*;
+
+  /// Checks that `day`'s generator, if it has one, always generates the same input for the same
+  /// seed, and that both parts solve it.
+  pub(crate) fn check_generator(day: &Day) {
+    for (size, seed) in [(1, 0), (10, 1), (200, 2)] {
+      // days don't need a generator, e.g. right after `aoc new`
+      let Some(input) = day.generate(size, seed) else {
+        continue;
+      };
+      assert_eq!(day.generate(size, seed).as_ref(), Some(&input));
+      for part in [1, 2] {
+        if let Err(e) = day.solve(part, &input) {
+          panic!("{day}, size {size}, seed {seed}:\n{e}");
+        }
+      }
+    }
+  }
+
+  #[test]
+  fn generated_inputs_are_valid() {
+    for day in DAYS {
+      check_generator(day);
+    }
+  }
+}
@@ -0,0 +1,112 @@
+use std::fmt;
+use std::str::FromStr;
+
+/// Malformed puzzle input, pointing at the line and column of the offending text.
+///
+/// Displays as a caret diagnostic:
+///
+/// ```text
+/// expected an integer, found `12x`
+///  --> 3:1
+///   |
+/// 3 | 12x
+///   | ^^^
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError {
+  /// 1-based line number.
+  pub line: usize,
+  /// 1-based column, counted in characters.
+  pub column: usize,
+  /// The offending text, limited to a single line.
+  pub found: String,
+  /// The full line containing the offending text.
+  pub text: String,
+  /// A description of what should have been there instead.
+  pub expected: String,
+  offset: usize,
+}
+
+impl ParseError {
+  /// Creates an error pointing at `fragment`, which should be a subslice of `source`.
+  ///
+  /// A `fragment` from elsewhere points at the start of `source`.
+  pub fn new(source: &str, fragment: &str, expected: impl Into<String>) -> Self {
+    Self::at(
+      source,
+      offset_in(source, fragment),
+      fragment,
+      expected.into(),
+    )
+  }
+
+  /// Creates an error pointing just past the end of `fragment`, e.g. for a missing delimiter.
+  pub fn after(source: &str, fragment: &str, expected: impl Into<String>) -> Self {
+    let end = offset_in(source, fragment) + fragment.len();
+    Self::at(source, end, "", expected.into())
+  }
+
+  /// Re-targets an error created against `fragment` to point into `source` instead,
+  /// where `fragment` is a subslice of `source`.
+  pub fn within(self, source: &str, fragment: &str) -> Self {
+    let offset = (offset_in(source, fragment) + self.offset).min(source.len());
+    Self::at(source, offset, &self.found, self.expected)
+  }
+
+  fn at(source: &str, offset: usize, found: &str, expected: String) -> Self {
+    let line_start = source[..offset].rfind('\n').map(|i| i + 1).unwrap_or(0);
+    let text = source[line_start..].lines().next().unwrap_or("");
+    let found = found.lines().next().unwrap_or("");
+
+    Self {
+      line: source[..offset].matches('\n').count() + 1,
+      column: source[line_start..offset].chars().count() + 1,
+      found: found.to_owned(),
+      text: text.to_owned(),
+      expected,
+      offset,
+    }
+  }
+}
+
+fn offset_in(source: &str, fragment: &str) -> usize {
+  let start = source.as_ptr() as usize;
+  let ptr = fragment.as_ptr() as usize;
+  if start <= ptr && ptr + fragment.len() <= start + source.len() {
+    ptr - start
+  } else {
+    0
+  }
+}
+
+/// Parses `fragment` as a `T`, reporting `expected` at `fragment` on failure.
+pub fn parse_as<T: FromStr>(source: &str, fragment: &str, expected: &str) -> Result<T, ParseError> {
+  fragment
+    .parse()
+    .map_err(|_| ParseError::new(source, fragment, expected))
+}
+
+impl fmt::Display for ParseError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    if self.found.is_empty() {
+      writeln!(f, "expected {}", self.expected)?;
+    } else {
+      writeln!(f, "expected {}, found `{}`", self.expected, self.found)?;
+    }
+
+    let gutter = self.line.to_string().len();
+    writeln!(f, "{:gutter$}--> {}:{}", "", self.line, self.column)?;
+    writeln!(f, "{:gutter$} |", "")?;
+    writeln!(f, "{} | {}", self.line, self.text)?;
+    write!(
+      f,
+      "{:gutter$} | {:pad$}{}",
+      "",
+      "",
+      "^".repeat(self.found.chars().count().max(1)),
+      pad = self.column - 1
+    )
+  }
+}
+
+impl std::error::Error for ParseError {}
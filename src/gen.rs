@@ -0,0 +1,89 @@
+//! Seeded generators of large puzzle inputs, for benchmarking and for finding blowups that the
+//! real inputs are too small to show:
+//!
+//! ```text
+//! $ cargo run --release -- gen 7 --size 20000 --seed 3 > big.txt
+//! $ cargo run --release -- 7 --input big.txt --bench
+//! ```
+//!
+//! Each day implements [`Solution::generate`](crate::Solution::generate), and decides what `size`
+//! means for it, e.g. the number of elves for day 1 or the number of directories for day 7.
+//! The same seed and size always produce the same input.
+
+use std::ops::RangeInclusive;
+
+/// A small, fast PRNG (SplitMix64). Not suitable for anything but test data.
+#[derive(Clone, Debug)]
+pub struct Rng {
+  state: u64,
+}
+
+impl Rng {
+  pub fn new(seed: u64) -> Self {
+    Self { state: seed }
+  }
+
+  pub fn u64(&mut self) -> u64 {
+    self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    let mut z = self.state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+  }
+
+  /// A number in `range`, which must not be empty.
+  pub fn range(&mut self, range: RangeInclusive<u64>) -> u64 {
+    let (start, end) = range.into_inner();
+    assert!(start <= end, "empty range {start}..={end}");
+    match (end - start).checked_add(1) {
+      // the slight bias of `%` doesn't matter here
+      Some(len) => start + self.u64() % len,
+      None => self.u64(),
+    }
+  }
+
+  /// An index into a slice of length `len`, which must not be zero.
+  pub fn index(&mut self, len: usize) -> usize {
+    self.range(0..=len as u64 - 1) as usize
+  }
+
+  /// True with a probability of `numerator / denominator`.
+  pub fn ratio(&mut self, numerator: u64, denominator: u64) -> bool {
+    self.range(1..=denominator) <= numerator
+  }
+
+  pub fn pick<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+    &items[self.index(items.len())]
+  }
+
+  pub fn shuffle<T>(&mut self, items: &mut [T]) {
+    for i in (1..items.len()).rev() {
+      items.swap(i, self.index(i + 1));
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn is_deterministic() {
+    let numbers = |seed| {
+      let mut rng = Rng::new(seed);
+      (0..8).map(|_| rng.range(10..=20)).collect::<Vec<_>>()
+    };
+    assert_eq!(numbers(1), numbers(1));
+    assert_ne!(numbers(1), numbers(2));
+    assert!(numbers(3).iter().all(|n| (10..=20).contains(n)));
+
+    let mut rng = Rng::new(0);
+    assert_eq!(rng.range(5..=5), 5);
+    rng.range(0..=u64::MAX);
+
+    let mut items = (0..10).collect::<Vec<_>>();
+    rng.shuffle(&mut items);
+    items.sort();
+    assert_eq!(items, (0..10).collect::<Vec<_>>());
+  }
+}
@@ -0,0 +1,321 @@
+//! 2D points, directions and bounding boxes.
+//!
+//! Coordinates follow screen conventions: `x` grows to the right, and `y` grows downwards.
+//! A [`Point`] is also used as a vector, e.g. the offset between two points.
+
+use std::fmt;
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Point<T> {
+  pub x: T,
+  pub y: T,
+}
+
+impl<T> Point<T> {
+  pub const fn new(x: T, y: T) -> Self {
+    Self { x, y }
+  }
+
+  /// Converts each coordinate into `U`, e.g. from `Point<usize>` to `Point<i64>`.
+  pub fn cast<U: From<T>>(self) -> Point<U> {
+    Point::new(self.x.into(), self.y.into())
+  }
+
+  /// Converts each coordinate into `U`, or returns `None` if either doesn't fit.
+  pub fn try_cast<U: TryFrom<T>>(self) -> Option<Point<U>> {
+    Some(Point::new(self.x.try_into().ok()?, self.y.try_into().ok()?))
+  }
+}
+
+impl<T: Copy + Ord + Sub<Output = T>> Point<T> {
+  /// The absolute difference along each axis.
+  pub fn abs_diff(self, other: Self) -> Self {
+    let diff = |a: T, b: T| if a > b { a - b } else { b - a };
+    Point::new(diff(self.x, other.x), diff(self.y, other.y))
+  }
+
+  pub fn manhattan(self, other: Self) -> T
+  where
+    T: Add<Output = T>,
+  {
+    let d = self.abs_diff(other);
+    d.x + d.y
+  }
+
+  pub fn chebyshev(self, other: Self) -> T {
+    let d = self.abs_diff(other);
+    d.x.max(d.y)
+  }
+}
+
+impl Point<i64> {
+  /// The point one step away in `dir`.
+  pub fn step(self, dir: Direction) -> Self {
+    self + dir.offset()
+  }
+
+  pub fn neighbors4(self) -> impl Iterator<Item = Self> {
+    Direction::ALL.into_iter().map(move |dir| self.step(dir))
+  }
+
+  pub fn neighbors8(self) -> impl Iterator<Item = Self> {
+    (-1..=1)
+      .flat_map(|dy| (-1..=1).map(move |dx| Point::new(dx, dy)))
+      .filter(|d| *d != Point::default())
+      .map(move |d| self + d)
+  }
+}
+
+impl Point<usize> {
+  /// The point one step away in `dir`, or `None` if that would make a coordinate negative.
+  pub fn step(self, dir: Direction) -> Option<Self> {
+    let d = dir.offset();
+    Some(Point::new(
+      self.x.checked_add_signed(d.x as isize)?,
+      self.y.checked_add_signed(d.y as isize)?,
+    ))
+  }
+}
+
+impl<T: Add<Output = T>> Add for Point<T> {
+  type Output = Self;
+
+  fn add(self, rhs: Self) -> Self::Output {
+    Point::new(self.x + rhs.x, self.y + rhs.y)
+  }
+}
+
+impl<T: Sub<Output = T>> Sub for Point<T> {
+  type Output = Self;
+
+  fn sub(self, rhs: Self) -> Self::Output {
+    Point::new(self.x - rhs.x, self.y - rhs.y)
+  }
+}
+
+impl<T: Copy + Mul<Output = T>> Mul<T> for Point<T> {
+  type Output = Self;
+
+  fn mul(self, rhs: T) -> Self::Output {
+    Point::new(self.x * rhs, self.y * rhs)
+  }
+}
+
+impl<T: Neg<Output = T>> Neg for Point<T> {
+  type Output = Self;
+
+  fn neg(self) -> Self::Output {
+    Point::new(-self.x, -self.y)
+  }
+}
+
+impl<T: AddAssign> AddAssign for Point<T> {
+  fn add_assign(&mut self, rhs: Self) {
+    self.x += rhs.x;
+    self.y += rhs.y;
+  }
+}
+
+impl<T: SubAssign> SubAssign for Point<T> {
+  fn sub_assign(&mut self, rhs: Self) {
+    self.x -= rhs.x;
+    self.y -= rhs.y;
+  }
+}
+
+impl<T> From<(T, T)> for Point<T> {
+  fn from((x, y): (T, T)) -> Self {
+    Point::new(x, y)
+  }
+}
+
+impl<T> From<Point<T>> for (T, T) {
+  fn from(p: Point<T>) -> Self {
+    (p.x, p.y)
+  }
+}
+
+impl<T: fmt::Display> fmt::Display for Point<T> {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "({}, {})", self.x, self.y)
+  }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Direction {
+  Up,
+  Down,
+  Left,
+  Right,
+}
+
+impl Direction {
+  pub const ALL: [Direction; 4] = [
+    Direction::Up,
+    Direction::Down,
+    Direction::Left,
+    Direction::Right,
+  ];
+
+  /// The unit vector pointing in this direction.
+  pub fn offset(self) -> Point<i64> {
+    match self {
+      Direction::Up => Point::new(0, -1),
+      Direction::Down => Point::new(0, 1),
+      Direction::Left => Point::new(-1, 0),
+      Direction::Right => Point::new(1, 0),
+    }
+  }
+
+  pub fn opposite(self) -> Self {
+    match self {
+      Direction::Up => Direction::Down,
+      Direction::Down => Direction::Up,
+      Direction::Left => Direction::Right,
+      Direction::Right => Direction::Left,
+    }
+  }
+
+  pub fn turn_left(self) -> Self {
+    match self {
+      Direction::Up => Direction::Left,
+      Direction::Left => Direction::Down,
+      Direction::Down => Direction::Right,
+      Direction::Right => Direction::Up,
+    }
+  }
+
+  pub fn turn_right(self) -> Self {
+    self.turn_left().opposite()
+  }
+}
+
+/// The smallest axis-aligned box containing a set of points, with inclusive bounds.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Bounds<T> {
+  pub min: Point<T>,
+  pub max: Point<T>,
+}
+
+impl<T: Copy + Ord> Bounds<T> {
+  /// Bounds containing only `p`.
+  pub fn new(p: Point<T>) -> Self {
+    Self { min: p, max: p }
+  }
+
+  /// Bounds containing every point, or `None` if there are no points.
+  pub fn from_points(points: impl IntoIterator<Item = Point<T>>) -> Option<Self> {
+    let mut points = points.into_iter();
+    let mut bounds = Self::new(points.next()?);
+    for p in points {
+      bounds.expand(p);
+    }
+    Some(bounds)
+  }
+
+  /// Grows the bounds to contain `p`.
+  pub fn expand(&mut self, p: Point<T>) {
+    self.min = Point::new(self.min.x.min(p.x), self.min.y.min(p.y));
+    self.max = Point::new(self.max.x.max(p.x), self.max.y.max(p.y));
+  }
+
+  pub fn contains(&self, p: Point<T>) -> bool {
+    self.min.x <= p.x && p.x <= self.max.x && self.min.y <= p.y && p.y <= self.max.y
+  }
+}
+
+impl<T> Bounds<T>
+where
+  T: Copy + Add<Output = T> + Sub<Output = T> + From<u8>,
+{
+  pub fn width(&self) -> T {
+    self.max.x - self.min.x + T::from(1)
+  }
+
+  pub fn height(&self) -> T {
+    self.max.y - self.min.y + T::from(1)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn arithmetic() {
+    let (a, b) = (Point::new(1i64, 2), Point::new(4, -2));
+    assert_eq!(a + b, Point::new(5, 0));
+    assert_eq!(a - b, Point::new(-3, 4));
+    assert_eq!(a * 3, Point::new(3, 6));
+    assert_eq!(-a, Point::new(-1, -2));
+
+    let mut c = a;
+    c += b;
+    assert_eq!(c, Point::new(5, 0));
+    c -= b;
+    assert_eq!(c, a);
+
+    assert_eq!(Point::new(3u32, 4).cast::<i64>(), Point::new(3i64, 4));
+    assert_eq!(Point::new(-1i64, 4).try_cast::<usize>(), None);
+    assert_eq!(<(i64, i64)>::from(a), (1, 2));
+  }
+
+  #[test]
+  fn distances() {
+    let (a, b) = (Point::new(1i64, 2), Point::new(4, -2));
+    assert_eq!(a.abs_diff(b), Point::new(3, 4));
+    assert_eq!(a.manhattan(b), 7);
+    assert_eq!(b.manhattan(a), 7);
+    assert_eq!(a.chebyshev(b), 4);
+    assert_eq!(a.chebyshev(a), 0);
+
+    // unsigned points never go negative
+    let (a, b) = (Point::new(5usize, 0), Point::new(0, 5));
+    assert_eq!(a.manhattan(b), 10);
+    assert_eq!(Point::new(0usize, 3).step(Direction::Left), None);
+    assert_eq!(
+      Point::new(0usize, 3).step(Direction::Up),
+      Some(Point::new(0, 2))
+    );
+  }
+
+  #[test]
+  fn directions() {
+    use Direction::*;
+
+    assert_eq!(Up.turn_left(), Left);
+    assert_eq!(Left.turn_left(), Down);
+    assert_eq!(Up.turn_right(), Right);
+    assert_eq!(Right.turn_right(), Down);
+    for dir in Direction::ALL {
+      assert_eq!(dir.turn_left().turn_right(), dir);
+      assert_eq!(dir.turn_left().turn_left(), dir.opposite());
+      assert_eq!(dir.opposite().opposite(), dir);
+      assert_eq!(dir.offset() + dir.opposite().offset(), Point::default());
+    }
+
+    let p = Point::new(0i64, 0);
+    assert_eq!(p.step(Down), Point::new(0, 1));
+    assert_eq!(p.neighbors4().count(), 4);
+    assert_eq!(p.neighbors8().filter(|n| p.chebyshev(*n) == 1).count(), 8);
+  }
+
+  #[test]
+  fn bounds() {
+    let mut bounds = Bounds::new(Point::new(2i64, 3));
+    assert_eq!((bounds.width(), bounds.height()), (1, 1));
+
+    bounds.expand(Point::new(-1, 5));
+    bounds.expand(Point::new(0, 4));
+    assert_eq!(bounds.min, Point::new(-1, 3));
+    assert_eq!(bounds.max, Point::new(2, 5));
+    assert_eq!((bounds.width(), bounds.height()), (4, 3));
+    assert!(bounds.contains(Point::new(0, 3)));
+    assert!(!bounds.contains(Point::new(3, 3)));
+
+    let points = [Point::new(2i64, 3), Point::new(-1, 5), Point::new(0, 4)];
+    assert_eq!(Bounds::from_points(points), Some(bounds));
+    assert_eq!(Bounds::<i64>::from_points([]), None);
+  }
+}
@@ -0,0 +1,434 @@
+//! 2D grids, stored densely in a `Vec` or sparsely in a `HashMap`.
+//!
+//! Dense grids are indexed by `(x, y)` with `(0, 0)` in the top-left corner.
+//! Sparse grids are unbounded in every direction, and are indexed by signed coordinates.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+use crate::geom::Point;
+use crate::ParseError;
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+  data: Vec<T>,
+  cols: usize,
+}
+
+impl<T: Clone> Grid<T> {
+  pub fn filled(rows: usize, cols: usize, value: T) -> Self {
+    Self {
+      data: vec![value; rows * cols],
+      cols,
+    }
+  }
+}
+
+impl<T: Clone + Default> Grid<T> {
+  pub fn new(rows: usize, cols: usize) -> Self {
+    Self::filled(rows, cols, T::default())
+  }
+}
+
+impl<T> Grid<T> {
+  /// Creates a grid from cells stored in row-major order.
+  ///
+  /// Panics if `data` can't be split into rows of `cols` cells.
+  pub fn from_vec(data: Vec<T>, cols: usize) -> Self {
+    assert!(
+      (cols == 0 && data.is_empty()) || (cols > 0 && data.len().is_multiple_of(cols)),
+      "{} cells can't form rows of {cols} cells",
+      data.len()
+    );
+    Self { data, cols }
+  }
+
+  /// Parses a character map, one row per line, converting each character with `f`.
+  ///
+  /// Panics if the rows are not all the same length.
+  pub fn parse(s: &str, mut f: impl FnMut(char) -> T) -> Self {
+    let mut data = vec![];
+    let mut cols = None;
+    for line in s.lines() {
+      let len = data.len();
+      data.extend(line.chars().map(&mut f));
+      let width = data.len() - len;
+      match cols {
+        None => cols = Some(width),
+        Some(cols) => assert_eq!(cols, width, "rows must all be the same length"),
+      }
+    }
+    Self::from_vec(data, cols.unwrap_or(0))
+  }
+
+  /// Like [`Grid::parse`], but `f` may reject a character, in which case the error
+  /// points at it and says it should have been `expected`. Ragged rows are rejected too.
+  pub fn try_parse(
+    s: &str,
+    expected: &str,
+    mut f: impl FnMut(char) -> Option<T>,
+  ) -> Result<Self, ParseError> {
+    let mut data = vec![];
+    let mut cols = None;
+    for line in s.lines() {
+      let len = data.len();
+      for (i, c) in line.char_indices() {
+        let cell = f(c).ok_or_else(|| ParseError::new(s, &line[i..i + c.len_utf8()], expected))?;
+        data.push(cell);
+      }
+      let width = data.len() - len;
+      match cols {
+        None => cols = Some(width),
+        Some(cols) if cols != width => {
+          let expected = format!("a row of {cols} cells");
+          return Err(ParseError::new(s, line, expected));
+        }
+        Some(_) => {}
+      }
+    }
+    Ok(Self::from_vec(data, cols.unwrap_or(0)))
+  }
+
+  pub fn rows(&self) -> usize {
+    self.data.len().checked_div(self.cols).unwrap_or(0)
+  }
+
+  pub fn cols(&self) -> usize {
+    self.cols
+  }
+
+  pub fn contains(&self, x: usize, y: usize) -> bool {
+    x < self.cols && y < self.rows()
+  }
+
+  pub fn get(&self, x: usize, y: usize) -> Option<&T> {
+    if self.contains(x, y) {
+      Some(&self.data[y * self.cols + x])
+    } else {
+      None
+    }
+  }
+
+  pub fn get_mut(&mut self, x: usize, y: usize) -> Option<&mut T> {
+    if self.contains(x, y) {
+      Some(&mut self.data[y * self.cols + x])
+    } else {
+      None
+    }
+  }
+
+  /// Panics if `(x, y)` is out of bounds.
+  pub fn set(&mut self, x: usize, y: usize, value: T) {
+    self[(x, y)] = value;
+  }
+
+  /// Panics if `y` is out of bounds.
+  pub fn row(&self, y: usize) -> impl DoubleEndedIterator<Item = &T> + '_ {
+    let rows = self.rows();
+    assert!(
+      y < rows,
+      "row {y} is out of bounds, the grid has {rows} rows"
+    );
+    self.data[y * self.cols..(y + 1) * self.cols].iter()
+  }
+
+  /// Panics if `x` is out of bounds.
+  pub fn col(&self, x: usize) -> impl DoubleEndedIterator<Item = &T> + '_ {
+    let cols = self.cols;
+    assert!(
+      x < cols,
+      "column {x} is out of bounds, the grid has {cols} columns"
+    );
+    (0..self.rows()).map(move |y| &self.data[y * self.cols + x])
+  }
+
+  /// Every cell along with its position, in row-major order.
+  pub fn iter(&self) -> impl Iterator<Item = ((usize, usize), &T)> + '_ {
+    let cols = self.cols;
+    self
+      .data
+      .iter()
+      .enumerate()
+      .map(move |(i, v)| ((i % cols, i / cols), v))
+  }
+
+  /// The position of the first cell matching `predicate`, in row-major order.
+  pub fn position(&self, mut predicate: impl FnMut(&T) -> bool) -> Option<(usize, usize)> {
+    self.iter().find(|(_, v)| predicate(v)).map(|(pos, _)| pos)
+  }
+
+  pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+    Grid {
+      data: self.data.iter().map(f).collect(),
+      cols: self.cols,
+    }
+  }
+
+  /// The in-bounds orthogonal neighbors of `(x, y)`: up, down, left, right.
+  pub fn neighbors4(&self, x: usize, y: usize) -> impl Iterator<Item = (usize, usize)> + '_ {
+    self.neighbors(x, y, &NEIGHBORS_4)
+  }
+
+  /// The in-bounds orthogonal and diagonal neighbors of `(x, y)`.
+  pub fn neighbors8(&self, x: usize, y: usize) -> impl Iterator<Item = (usize, usize)> + '_ {
+    self.neighbors(x, y, &NEIGHBORS_8)
+  }
+
+  fn neighbors<'a>(
+    &'a self,
+    x: usize,
+    y: usize,
+    offsets: &'a [(isize, isize)],
+  ) -> impl Iterator<Item = (usize, usize)> + 'a {
+    offsets.iter().filter_map(move |&(dx, dy)| {
+      let (x, y) = (x.checked_add_signed(dx)?, y.checked_add_signed(dy)?);
+      self.contains(x, y).then_some((x, y))
+    })
+  }
+}
+
+const NEIGHBORS_4: [(isize, isize); 4] = [(0, -1), (0, 1), (-1, 0), (1, 0)];
+const NEIGHBORS_8: [(isize, isize); 8] = [
+  (0, -1),
+  (0, 1),
+  (-1, 0),
+  (1, 0),
+  (-1, -1),
+  (1, -1),
+  (-1, 1),
+  (1, 1),
+];
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+  type Output = T;
+
+  fn index(&self, (x, y): (usize, usize)) -> &Self::Output {
+    self
+      .get(x, y)
+      .unwrap_or_else(|| panic!("({x}, {y}) is out of bounds"))
+  }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+  fn index_mut(&mut self, (x, y): (usize, usize)) -> &mut Self::Output {
+    self
+      .get_mut(x, y)
+      .unwrap_or_else(|| panic!("({x}, {y}) is out of bounds"))
+  }
+}
+
+impl<T> Index<Point<usize>> for Grid<T> {
+  type Output = T;
+
+  fn index(&self, p: Point<usize>) -> &Self::Output {
+    &self[(p.x, p.y)]
+  }
+}
+
+impl<T> IndexMut<Point<usize>> for Grid<T> {
+  fn index_mut(&mut self, p: Point<usize>) -> &mut Self::Output {
+    &mut self[(p.x, p.y)]
+  }
+}
+
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    for y in 0..self.rows() {
+      for cell in self.row(y) {
+        write!(f, "{cell}")?;
+      }
+      writeln!(f)?;
+    }
+
+    Ok(())
+  }
+}
+
+/// A grid which only stores cells that were set, every other cell holds `default`.
+#[derive(Clone, Debug)]
+pub struct SparseGrid<T> {
+  data: HashMap<(i64, i64), T>,
+  default: T,
+  min: (i64, i64),
+  max: (i64, i64),
+}
+
+impl<T: Default> Default for SparseGrid<T> {
+  fn default() -> Self {
+    Self::new(T::default())
+  }
+}
+
+impl<T> SparseGrid<T> {
+  pub fn new(default: T) -> Self {
+    Self {
+      data: HashMap::new(),
+      default,
+      min: (i64::MAX, i64::MAX),
+      max: (i64::MIN, i64::MIN),
+    }
+  }
+
+  pub fn get(&self, x: i64, y: i64) -> &T {
+    self.data.get(&(x, y)).unwrap_or(&self.default)
+  }
+
+  pub fn set(&mut self, x: i64, y: i64, value: T) {
+    self.min = (self.min.0.min(x), self.min.1.min(y));
+    self.max = (self.max.0.max(x), self.max.1.max(y));
+    self.data.insert((x, y), value);
+  }
+
+  /// The number of cells that were set.
+  pub fn len(&self) -> usize {
+    self.data.len()
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.data.is_empty()
+  }
+
+  /// The smallest and largest coordinates of any cell that was set.
+  pub fn bounds(&self) -> Option<((i64, i64), (i64, i64))> {
+    (!self.is_empty()).then_some((self.min, self.max))
+  }
+
+  pub fn iter(&self) -> impl Iterator<Item = ((i64, i64), &T)> + '_ {
+    self.data.iter().map(|(pos, v)| (*pos, v))
+  }
+}
+
+impl<T: fmt::Display> fmt::Display for SparseGrid<T> {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let Some((min, max)) = self.bounds() else {
+      return Ok(());
+    };
+
+    for y in min.1..=max.1 {
+      for x in min.0..=max.0 {
+        write!(f, "{}", self.get(x, y))?;
+      }
+      writeln!(f)?;
+    }
+
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  /// 3 columns by 2 rows:
+  ///
+  /// ```text
+  /// abc
+  /// def
+  /// ```
+  fn letters() -> Grid<char> {
+    Grid::parse("abc\ndef", |c| c)
+  }
+
+  #[test]
+  fn bounds() {
+    let mut grid = letters();
+    assert_eq!((grid.cols(), grid.rows()), (3, 2));
+    assert_eq!(grid.get(2, 1), Some(&'f'));
+    assert_eq!(grid.get(3, 0), None);
+    assert_eq!(grid.get(0, 2), None);
+    assert_eq!(grid.get_mut(3, 1), None);
+
+    grid.set(1, 0, 'x');
+    grid[Point::new(0, 1)] = 'y';
+    assert_eq!(grid.to_string(), "axc\nyef\n");
+  }
+
+  #[test]
+  #[should_panic = "(0, 2) is out of bounds"]
+  fn set_out_of_bounds() {
+    letters().set(0, 2, 'x');
+  }
+
+  #[test]
+  fn neighbors() {
+    let grid = Grid::<u8>::new(3, 3);
+    let neighbors = |iter: &mut dyn Iterator<Item = (usize, usize)>| {
+      let mut neighbors = iter.collect::<Vec<_>>();
+      neighbors.sort();
+      neighbors
+    };
+
+    // corner
+    assert_eq!(neighbors(&mut grid.neighbors4(0, 0)), [(0, 1), (1, 0)]);
+    assert_eq!(
+      neighbors(&mut grid.neighbors8(2, 2)),
+      [(1, 1), (1, 2), (2, 1)]
+    );
+    // edge
+    assert_eq!(
+      neighbors(&mut grid.neighbors4(1, 0)),
+      [(0, 0), (1, 1), (2, 0)]
+    );
+    assert_eq!(grid.neighbors8(0, 1).count(), 5);
+    // middle
+    assert_eq!(grid.neighbors4(1, 1).count(), 4);
+    assert_eq!(grid.neighbors8(1, 1).count(), 8);
+  }
+
+  #[test]
+  fn try_parse() {
+    let digit = |c: char| c.to_digit(10);
+    let grid = Grid::try_parse("12\n34\n56", "a digit", digit).unwrap();
+    assert_eq!((grid.cols(), grid.rows()), (2, 3));
+    assert_eq!(grid[(1, 2)], 6);
+
+    let e = Grid::try_parse("12\n345", "a digit", digit).unwrap_err();
+    assert_eq!((e.line, e.column), (2, 1));
+    assert!(e.to_string().contains("a row of 2 cells"));
+
+    let e = Grid::try_parse("12\n3x", "a digit", digit).unwrap_err();
+    assert_eq!((e.line, e.column), (2, 2));
+  }
+
+  #[test]
+  fn rows_and_cols() {
+    let grid = letters();
+    assert_eq!(grid.row(1).collect::<String>(), "def");
+    assert_eq!(grid.row(0).rev().collect::<String>(), "cba");
+    assert_eq!(grid.col(2).collect::<String>(), "cf");
+    assert_eq!(grid.position(|&c| c == 'e'), Some((1, 1)));
+    assert_eq!(
+      grid.iter().map(|(pos, _)| pos).collect::<Vec<_>>(),
+      [(0, 0), (1, 0), (2, 0), (0, 1), (1, 1), (2, 1)]
+    );
+  }
+
+  #[test]
+  #[should_panic = "row 2 is out of bounds, the grid has 2 rows"]
+  fn row_out_of_bounds() {
+    let _ = letters().row(2);
+  }
+
+  #[test]
+  #[should_panic = "column 3 is out of bounds, the grid has 3 columns"]
+  fn col_out_of_bounds() {
+    // would otherwise read the first cell of the next row
+    let _ = letters().col(3);
+  }
+
+  #[test]
+  fn sparse() {
+    let mut grid = SparseGrid::new('.');
+    assert_eq!(grid.get(-5, 7), &'.');
+    assert_eq!(grid.bounds(), None);
+    assert_eq!(grid.to_string(), "");
+
+    grid.set(-1, 0, '#');
+    grid.set(1, 1, '#');
+    assert_eq!(grid.len(), 2);
+    assert_eq!(grid.bounds(), Some(((-1, 0), (1, 1))));
+    assert_eq!(grid.to_string(), "#..\n..#\n");
+    assert_eq!(SparseGrid::<u8>::default().get(0, 0), &0);
+  }
+}
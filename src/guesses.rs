@@ -0,0 +1,153 @@
+//! Wrong answers submitted for each day, so that they are never submitted again.
+//!
+//! A guesses file has one guess per line, with the part, the verdict and the answer:
+//!
+//! ```text
+//! 1 too-high 68924
+//! 2 wrong ABCDEFGH
+//! ```
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use anyhow::Context;
+
+use crate::client::Verdict;
+
+pub fn default_path(day: u32) -> PathBuf {
+  crate::input::day_dir(day).join("guesses.txt")
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Guess {
+  pub part: u8,
+  pub verdict: Verdict,
+  pub answer: String,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Guesses {
+  guesses: Vec<Guess>,
+}
+
+impl Guesses {
+  /// Loads guesses from `path`. A missing file means nothing was guessed yet.
+  pub fn load(path: &Path) -> anyhow::Result<Self> {
+    if !path.exists() {
+      return Ok(Self::default());
+    }
+
+    std::fs::read_to_string(path)
+      .with_context(|| format!("failed to read `{}`", path.display()))?
+      .parse()
+      .with_context(|| format!("invalid guesses file `{}`", path.display()))
+  }
+
+  pub fn save(&self, path: &Path) -> anyhow::Result<()> {
+    std::fs::write(path, self.to_string())
+      .with_context(|| format!("failed to write `{}`", path.display()))
+  }
+
+  pub fn iter(&self) -> impl Iterator<Item = &Guess> + '_ {
+    self.guesses.iter()
+  }
+
+  /// The verdict `answer` would get, if earlier guesses already rule it out.
+  ///
+  /// Besides exact repeats, a numeric answer is too high if it is at least a guess
+  /// that was too high, and too low if it is at most a guess that was too low.
+  pub fn verdict(&self, part: u8, answer: &str) -> Option<Verdict> {
+    let number = answer.parse::<i128>().ok();
+    self
+      .guesses
+      .iter()
+      .filter(|guess| guess.part == part)
+      .find_map(|guess| {
+        if guess.answer == answer {
+          return Some(guess.verdict.clone());
+        }
+        let ordering = number?.cmp(&guess.answer.parse::<i128>().ok()?);
+        match (&guess.verdict, ordering) {
+          (Verdict::TooHigh, Ordering::Greater) => Some(Verdict::TooHigh),
+          (Verdict::TooLow, Ordering::Less) => Some(Verdict::TooLow),
+          _ => None,
+        }
+      })
+  }
+
+  pub fn record(&mut self, part: u8, answer: impl Into<String>, verdict: Verdict) {
+    self.guesses.push(Guess {
+      part,
+      verdict,
+      answer: answer.into(),
+    });
+  }
+}
+
+impl FromStr for Guesses {
+  type Err = anyhow::Error;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    let mut guesses = Guesses::default();
+    for (n, line) in s.lines().enumerate() {
+      if line.trim().is_empty() {
+        continue;
+      }
+
+      let mut fields = line.splitn(3, ' ');
+      let (Some(part), Some(verdict), Some(answer)) = (fields.next(), fields.next(), fields.next())
+      else {
+        anyhow::bail!("line {}: expected `<part> <verdict> <answer>`", n + 1);
+      };
+      let part = match part {
+        "1" => 1,
+        "2" => 2,
+        _ => anyhow::bail!("line {}: unknown part `{part}`", n + 1),
+      };
+      let verdict = verdict.parse().with_context(|| format!("line {}", n + 1))?;
+      guesses.record(part, answer, verdict);
+    }
+    Ok(guesses)
+  }
+}
+
+impl fmt::Display for Guesses {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    for guess in self.guesses.iter() {
+      writeln!(f, "{} {} {}", guess.part, guess.verdict, guess.answer)?;
+    }
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn numeric_guesses_bound_the_answer() {
+    let mut guesses = Guesses::default();
+    guesses.record(1, "100", Verdict::TooHigh);
+    guesses.record(1, "10", Verdict::TooLow);
+    guesses.record(2, "ABC", Verdict::Wrong);
+
+    assert_eq!(guesses.verdict(1, "100"), Some(Verdict::TooHigh));
+    assert_eq!(guesses.verdict(1, "250"), Some(Verdict::TooHigh));
+    assert_eq!(guesses.verdict(1, "-3"), Some(Verdict::TooLow));
+    assert_eq!(guesses.verdict(1, "50"), None);
+    assert_eq!(guesses.verdict(2, "ABC"), Some(Verdict::Wrong));
+    assert_eq!(guesses.verdict(2, "100"), None);
+  }
+
+  #[test]
+  fn round_trips() {
+    let s = "1 too-high 68924\n2 wrong ABC DEF\n";
+    let guesses = s.parse::<Guesses>().unwrap();
+    assert_eq!(guesses.iter().nth(1).unwrap().answer, "ABC DEF");
+    assert_eq!(guesses.to_string(), s);
+    assert!("3 wrong 1".parse::<Guesses>().is_err());
+    assert!("1 correct 1".parse::<Guesses>().is_err());
+  }
+}
@@ -0,0 +1,34 @@
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+use anyhow::Context;
+
+/// The directory holding every day's module, and the registry in `mod.rs`.
+pub fn days_dir() -> PathBuf {
+  Path::new(env!("CARGO_MANIFEST_DIR")).join("src/days")
+}
+
+/// The directory holding a day's source and data files.
+///
+/// Every variant of a day (e.g. `day13-bump`) shares the files of `day{day}`.
+pub fn day_dir(day: u32) -> PathBuf {
+  days_dir().join(format!("day{day}"))
+}
+
+/// Where a day's input is read from when no path is given on the command line.
+pub fn default_path(day: u32) -> PathBuf {
+  day_dir(day).join("input.txt")
+}
+
+/// Reads puzzle input from `path`, or from stdin if `path` is `-`.
+pub fn read(path: &Path) -> anyhow::Result<String> {
+  if path == Path::new("-") {
+    let mut input = String::new();
+    std::io::stdin()
+      .read_to_string(&mut input)
+      .context("failed to read input from stdin")?;
+    return Ok(input);
+  }
+
+  std::fs::read_to_string(path).with_context(|| format!("failed to read `{}`", path.display()))
+}
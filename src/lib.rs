@@ -1,13 +1,46 @@
-use std::time::Instant;
-
-pub fn time<F, H, R>(f: F, then: H)
-where
-  F: FnOnce() -> R,
-  H: FnOnce(R),
-{
-  let start = Instant::now();
-  let r = f();
-  let time = Instant::now() - start;
-  then(r);
-  println!("Done in {} microseconds\n", time.as_micros());
+use std::fmt::Display;
+
+pub mod alloc;
+pub mod answers;
+pub mod bench;
+pub mod client;
+pub mod cursor;
+pub mod days;
+pub mod error;
+pub mod gen;
+pub mod geom;
+pub mod grid;
+pub mod guesses;
+pub mod input;
+pub mod parse;
+pub mod pool;
+pub mod record;
+pub mod scaffold;
+pub mod step;
+pub mod summary;
+#[cfg(test)]
+mod testing;
+pub mod viz;
+
+pub use error::ParseError;
+
+/// A single day's puzzle.
+///
+/// `parse` turns the raw puzzle input into the representation shared by both parts,
+/// and rejects malformed input with a [`ParseError`].
+pub trait Solution {
+  type Input<'a>;
+  type Output1: Display;
+  type Output2: Display;
+
+  fn parse(input: &str) -> Result<Self::Input<'_>, ParseError>;
+  fn part1(input: &Self::Input<'_>) -> Self::Output1;
+  fn part2(input: &Self::Input<'_>) -> Self::Output2;
+
+  /// Generates a valid input of roughly `size` units, where each day decides what a unit is.
+  /// See [`gen`]. Returns `None` for days without a generator.
+  fn generate(rng: &mut gen::Rng, size: usize) -> Option<String> {
+    let _ = (rng, size);
+    None
+  }
 }
@@ -0,0 +1,643 @@
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+use anyhow::Context;
+use aoc::answers::{self, Answers};
+use aoc::bench;
+use aoc::client::{self, Client, Fetched, Verdict};
+use aoc::days::day1;
+use aoc::days::day10::{self, Day10};
+use aoc::days::day11::{self, Day11};
+use aoc::days::day14::{self, Day14};
+use aoc::days::day2::{Day2, Interpretations};
+use aoc::days::day5::{self, Day5};
+use aoc::days::day9::{self, Day9};
+use aoc::days::{Selector, DAYS};
+use aoc::guesses::{self, Guesses};
+use aoc::pool;
+use aoc::record::{Record, Status};
+use aoc::step::{self, Stepper};
+use aoc::summary::Summary;
+use aoc::viz::{self, Recorder};
+use aoc::Solution;
+use clap::{Parser, Subcommand, ValueEnum};
+
+/// Run Advent of Code 2022 solutions.
+#[derive(Parser)]
+#[command(args_conflicts_with_subcommands = true)]
+struct Args {
+  #[command(subcommand)]
+  command: Option<Command>,
+  /// Days to run, e.g. `7`, `5..9`, `5..=9` or `13-bump`. Runs every day if omitted.
+  days: Vec<Selector>,
+  /// Only run this part.
+  #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=2))]
+  part: Option<u8>,
+  /// Read input from this file, or from stdin if `-`. Only valid when running a single day.
+  /// Defaults to `src/days/day{N}/input.txt`.
+  #[arg(short, long)]
+  input: Option<PathBuf>,
+  /// Benchmark each part, timing parsing and solving separately.
+  #[arg(short, long)]
+  bench: bool,
+  /// Number of timed runs per part when benchmarking.
+  #[arg(long, default_value_t = 100, requires = "bench")]
+  runs: usize,
+  /// Number of untimed runs per part before benchmarking.
+  #[arg(long, default_value_t = 10, requires = "bench")]
+  warmup: usize,
+  /// Compare answers against the known answers, and fail if any of them changed.
+  #[arg(short, long)]
+  check: bool,
+  /// Read known answers from this file. Only valid when running a single day.
+  /// Defaults to `src/days/day{N}/answers.txt`, unless `--input` is given.
+  #[arg(short, long)]
+  answers: Option<PathBuf>,
+  /// Record the computed answers as the known answers.
+  #[arg(long, conflicts_with = "check")]
+  save_answers: bool,
+  /// Run one day at a time on the main thread, instead of on a thread per core,
+  /// so that days don't compete for the CPU while being timed.
+  #[arg(short, long)]
+  sequential: bool,
+  /// Number of days to run at once. Defaults to the number of cores.
+  #[arg(short, long, conflicts_with = "sequential", value_parser = clap::value_parser!(u32).range(1..))]
+  jobs: Option<u32>,
+  /// How to print results. `json` prints one JSON object per day and part.
+  #[arg(short, long, value_enum, default_value_t = Format::Text)]
+  format: Format,
+}
+
+#[derive(Subcommand)]
+enum Command {
+  /// Download a day's input to `src/days/day{N}/input.txt`, unless it's already there.
+  ///
+  /// Reads the session token from `AOC_SESSION`, or from a `.session` file in the repository root.
+  Fetch {
+    #[arg(value_parser = clap::value_parser!(u32).range(1..=25))]
+    day: u32,
+  },
+  /// Create `src/days/day{N}` from `templates/day.rs`, with empty input and example files,
+  /// and add the day to the runner.
+  New {
+    #[arg(value_parser = clap::value_parser!(u32).range(1..=25))]
+    day: u32,
+  },
+  /// Record a day's simulation as numbered frames, e.g. `frames/00042.png`. Supports days 9 and 14.
+  Animate {
+    #[arg(value_parser = clap::value_parser!(u32).range(1..=25))]
+    day: u32,
+    #[arg(short, long, default_value_t = 1, value_parser = clap::value_parser!(u8).range(1..=2))]
+    part: u8,
+    /// Directory to write the frames to.
+    #[arg(short, long)]
+    out: PathBuf,
+    /// One of `ascii`, `ansi`, `ppm` or `png`.
+    #[arg(short, long, default_value = "png")]
+    format: viz::Format,
+    /// Only keep every Nth frame.
+    #[arg(long, default_value_t = 1)]
+    every: usize,
+    /// Size of each cell in pixels, for `ppm` and `png`.
+    #[arg(long, default_value_t = 4)]
+    scale: usize,
+  },
+  /// Generate a large, valid input for a day, for benchmarking with `--input`.
+  Gen {
+    #[arg(value_parser = clap::value_parser!(u32).range(1..=25))]
+    day: u32,
+    /// How big the input should be. What this counts depends on the day, e.g. elves for day 1.
+    #[arg(long, default_value_t = 1000)]
+    size: usize,
+    /// The same seed and size always produce the same input.
+    #[arg(long, default_value_t = 0)]
+    seed: u64,
+    /// Write the input to this file instead of stdout.
+    #[arg(short, long)]
+    out: Option<PathBuf>,
+  },
+  /// Step through a day's simulation interactively. Supports days 5, 9, 10, 11 and 14.
+  ///
+  /// Type `help` at the prompt for the commands.
+  Debug {
+    #[arg(value_parser = clap::value_parser!(u32).range(1..=25))]
+    day: u32,
+    #[arg(short, long, default_value_t = 1, value_parser = clap::value_parser!(u8).range(1..=2))]
+    part: u8,
+    /// Read input from this file instead of `src/days/day{N}/input.txt`.
+    #[arg(short, long)]
+    input: Option<PathBuf>,
+  },
+  /// Print the elves carrying the most calories for day 1, reading the input as a stream,
+  /// so that inputs of any size fit in memory.
+  Calories {
+    /// How many elves to print.
+    #[arg(long, default_value_t = 3)]
+    top: usize,
+    /// Read input from this file, or from stdin if `-`, instead of `src/days/day1/input.txt`.
+    #[arg(short, long)]
+    input: Option<PathBuf>,
+    /// Print statistics about every elf instead: item counts, the mean, median and percentiles
+    /// of their calories, the top elves with ties, and a histogram. Keeps every elf in memory.
+    #[arg(long)]
+    report: bool,
+    /// Number of bars in the histogram of `--report`.
+    #[arg(long, default_value_t = 10, requires = "report")]
+    bins: usize,
+    /// Skip malformed lines with a warning, instead of stopping at the first one.
+    #[arg(long)]
+    lenient: bool,
+  },
+  /// Score day 2's strategy guide for every meaning of `X`, `Y` and `Z`, as shapes or as
+  /// outcomes, and print the best one first.
+  Strategy {
+    /// Read input from this file instead of `src/days/day2/input.txt`.
+    #[arg(short, long)]
+    input: Option<PathBuf>,
+  },
+  /// Solve a part with the day's input and submit the answer.
+  ///
+  /// Wrong answers are remembered in `src/days/day{N}/guesses.txt` and never submitted twice.
+  /// A correct answer is recorded as the known answer.
+  Submit {
+    #[arg(value_parser = clap::value_parser!(u32).range(1..=25))]
+    day: u32,
+    #[arg(value_parser = clap::value_parser!(u8).range(1..=2))]
+    part: u8,
+  },
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Format {
+  Text,
+  Json,
+}
+
+fn main() -> anyhow::Result<()> {
+  let args = Args::parse();
+
+  match args.command {
+    Some(Command::Fetch { day }) => fetch(day),
+    Some(Command::New { day }) => new(day),
+    Some(Command::Animate {
+      day,
+      part,
+      out,
+      format,
+      every,
+      scale,
+    }) => {
+      let recorder = Recorder::new(out, format)?.every(every).scale(scale);
+      animate(day, part, recorder)
+    }
+    Some(Command::Gen {
+      day,
+      size,
+      seed,
+      out,
+    }) => gen(day, size, seed, out),
+    Some(Command::Debug { day, part, input }) => debug(day, part, input),
+    Some(Command::Calories {
+      top,
+      input,
+      report,
+      bins,
+      lenient,
+    }) => {
+      let elves = read_elves(input, lenient)?;
+      match report {
+        true => calorie_report(elves, top, bins),
+        false => calories(elves, top),
+      }
+    }
+    Some(Command::Strategy { input }) => strategy(input),
+    Some(Command::Submit { day, part }) => submit(day, part),
+    None => run(args),
+  }
+}
+
+fn fetch(day: u32) -> anyhow::Result<()> {
+  let path = aoc::input::default_path(day);
+  // don't insist on a session token when there's nothing to download
+  if client::is_cached(&path) {
+    println!("Day {day} input is already at `{}`", path.display());
+    return Ok(());
+  }
+
+  match Client::from_env()?.fetch_input(day, &path)? {
+    Fetched::Cached(path) => println!("Day {day} input is already at `{}`", path.display()),
+    Fetched::Downloaded(path) => println!("Downloaded day {day} input to `{}`", path.display()),
+  }
+  Ok(())
+}
+
+fn new(day: u32) -> anyhow::Result<()> {
+  let created = aoc::scaffold::create(&aoc::input::days_dir(), day)?;
+  println!("Created `{}`", created.module.display());
+  println!("Added day {day} to `{}`", created.registry.display());
+  Ok(())
+}
+
+fn gen(day: u32, size: usize, seed: u64, out: Option<PathBuf>) -> anyhow::Result<()> {
+  let Some(solution) = DAYS.iter().find(|d| d.number == day && d.is_default()) else {
+    anyhow::bail!("day {day} has no solution yet");
+  };
+  let Some(input) = solution.generate(size, seed) else {
+    anyhow::bail!("day {day} has no input generator");
+  };
+  match out {
+    Some(path) => {
+      std::fs::write(&path, input + "\n")
+        .with_context(|| format!("failed to write `{}`", path.display()))?;
+      eprintln!("Wrote day {day} input to `{}`", path.display());
+    }
+    None => println!("{input}"),
+  }
+  Ok(())
+}
+
+fn animate(day: u32, part: u8, mut recorder: Recorder) -> anyhow::Result<()> {
+  let input = aoc::input::read(&aoc::input::default_path(day))?;
+  let invalid = |e| anyhow::anyhow!("day {day} has invalid input: {e}");
+  match day {
+    9 => {
+      let moves = Day9::parse(&input).map_err(invalid)?;
+      day9::animate(&moves, if part == 1 { 1 } else { 9 }, &mut recorder)?;
+    }
+    14 => {
+      let grid = Day14::parse(&input).map_err(invalid)?;
+      day14::animate(&grid, part, &mut recorder)?;
+    }
+    _ => anyhow::bail!("day {day} has no animation"),
+  }
+  println!("Recorded {} frame(s)", recorder.frames());
+  Ok(())
+}
+
+fn debug(day: u32, part: u8, input: Option<PathBuf>) -> anyhow::Result<()> {
+  let path = input.unwrap_or_else(|| aoc::input::default_path(day));
+  let input = aoc::input::read(&path)?;
+  let invalid = |e| anyhow::anyhow!("day {day} has invalid input: {e}");
+  let repl = |stepper: &mut dyn Stepper| {
+    step::repl(stepper, std::io::stdin().lock(), std::io::stdout().lock())
+  };
+  match day {
+    5 => repl(&mut *day5::stepper(
+      &Day5::parse(&input).map_err(invalid)?,
+      part,
+    ))?,
+    9 => repl(&mut *day9::stepper(
+      &Day9::parse(&input).map_err(invalid)?,
+      part,
+    ))?,
+    10 => repl(&mut *day10::stepper(
+      &Day10::parse(&input).map_err(invalid)?,
+      part,
+    ))?,
+    11 => repl(&mut *day11::stepper(
+      &Day11::parse(&input).map_err(invalid)?,
+      part,
+    ))?,
+    14 => repl(&mut *day14::stepper(
+      &Day14::parse(&input).map_err(invalid)?,
+      part,
+    ))?,
+    _ => anyhow::bail!("day {day} can't be stepped through"),
+  }
+  Ok(())
+}
+
+type Elves = Box<dyn Iterator<Item = Result<day1::Elf, day1::StreamError>>>;
+
+fn calories(elves: Elves, k: usize) -> anyhow::Result<()> {
+  let top = day1::top_k(elves, k)?;
+  for (rank, total) in top.iter().enumerate() {
+    println!("{:>4}. {total}", rank + 1);
+  }
+  println!("Total: {}", top.iter().map(|&n| n as u128).sum::<u128>());
+  Ok(())
+}
+
+fn calorie_report(elves: Elves, k: usize, bins: usize) -> anyhow::Result<()> {
+  let elves = elves.collect::<Result<Vec<_>, _>>()?;
+  if elves.is_empty() {
+    anyhow::bail!("there are no elves to report on");
+  }
+  println!("{}", day1::Report::new(&elves, k, bins));
+  Ok(())
+}
+
+/// Reads day 1's elves line by line, see `calories`.
+fn read_elves(input: Option<PathBuf>, lenient: bool) -> anyhow::Result<Elves> {
+  let path = input.unwrap_or_else(|| aoc::input::default_path(1));
+  let reader: Box<dyn BufRead> = if path == Path::new("-") {
+    Box::new(std::io::stdin().lock())
+  } else {
+    let file = File::open(&path).with_context(|| format!("failed to open `{}`", path.display()))?;
+    Box::new(BufReader::new(file))
+  };
+
+  let elves = day1::Elves::new(reader);
+  if lenient {
+    let warn = |e| eprintln!("warning: skipped a malformed line: {e}");
+    Ok(Box::new(day1::skip_malformed(elves, warn)))
+  } else {
+    Ok(Box::new(elves))
+  }
+}
+
+fn strategy(input: Option<PathBuf>) -> anyhow::Result<()> {
+  let path = input.unwrap_or_else(|| aoc::input::default_path(2));
+  let input = aoc::input::read(&path)?;
+  let guide = Day2::parse(&input).map_err(|e| anyhow::anyhow!("day 2 has invalid input: {e}"))?;
+  println!("{}", Interpretations::new(&guide));
+  Ok(())
+}
+
+fn submit(day: u32, part: u8) -> anyhow::Result<()> {
+  let Some(day) = DAYS.iter().find(|d| d.number == day && d.is_default()) else {
+    anyhow::bail!("day {day} has no solution yet");
+  };
+
+  let path = day.default_input();
+  if !client::is_cached(&path) {
+    anyhow::bail!(
+      "{day} has no input at `{}`, run `aoc fetch {}` first",
+      path.display(),
+      day.number
+    );
+  }
+  let input = aoc::input::read(&path)?;
+  let answer = match day.solve(part, &input) {
+    Ok(answer) => answer,
+    Err(e) => anyhow::bail!("{day} has invalid input: {e}"),
+  };
+  // e.g. day 10's CRT image has to be read by a human
+  if answer.contains('\n') {
+    anyhow::bail!("{day} part {part} answer spans multiple lines, submit it by hand:\n{answer}");
+  }
+  print_answer(&format!("{day} part {part}"), &answer);
+
+  let guesses_path = guesses::default_path(day.number);
+  let mut guesses = Guesses::load(&guesses_path)?;
+  let submission = Client::from_env()?.submit(day.number, part, &answer, &mut guesses)?;
+  guesses.save(&guesses_path)?;
+
+  let verdict = submission.verdict;
+  let note = if submission.sent {
+    ""
+  } else {
+    " (already guessed, not sent)"
+  };
+  match &verdict {
+    Verdict::Correct => println!("That's the right answer!"),
+    Verdict::TooHigh => println!("That's not the right answer, it's too high{note}"),
+    Verdict::TooLow => println!("That's not the right answer, it's too low{note}"),
+    Verdict::Wrong => println!("That's not the right answer{note}"),
+    Verdict::RateLimited(Some(wait)) => println!("Answered too recently, wait {wait}"),
+    Verdict::RateLimited(None) => println!("Answered too recently, wait a bit"),
+    Verdict::WrongLevel => println!("{day} part {part} is already solved, or not unlocked yet"),
+  }
+
+  if verdict == Verdict::Correct {
+    let answers_path = day.default_answers();
+    let mut answers = Answers::load(&answers_path)?;
+    answers.set(part, answer);
+    answers.save(&answers_path)?;
+  }
+  Ok(())
+}
+
+fn run(args: Args) -> anyhow::Result<()> {
+  let parts = match args.part {
+    Some(part) => vec![part],
+    None => vec![1, 2],
+  };
+
+  let days = DAYS
+    .iter()
+    .filter(|day| {
+      if args.days.is_empty() {
+        day.is_default()
+      } else {
+        args.days.iter().any(|s| s.matches(day))
+      }
+    })
+    .collect::<Vec<_>>();
+
+  for (flag, set) in [
+    ("--input", args.input.is_some()),
+    ("--answers", args.answers.is_some()),
+  ] {
+    if set && days.len() != 1 {
+      anyhow::bail!(
+        "`{flag}` requires exactly one day, but {} were selected",
+        days.len()
+      );
+    }
+  }
+
+  if args.save_answers && args.input.is_some() && args.answers.is_none() {
+    anyhow::bail!("`--save-answers` with `--input` requires `--answers`");
+  }
+
+  // without `--bench`, each part is run exactly once
+  let config = if args.bench {
+    bench::Config {
+      warmup: args.warmup,
+      runs: args.runs,
+    }
+  } else {
+    bench::Config { warmup: 0, runs: 1 }
+  };
+  let json = args.format == Format::Json;
+  let emit = |record: Record| println!("{}", serde_json::to_string(&record).unwrap());
+
+  // read every input up front, so that only solving happens on the worker threads
+  let mut jobs = vec![];
+  for day in days {
+    let input = match &args.input {
+      Some(path) => Ok(aoc::input::read(path)?),
+      None => {
+        let path = day.default_input();
+        if path.exists() {
+          Ok(aoc::input::read(&path)?)
+        } else {
+          Err(format!("no input at `{}`", path.display()))
+        }
+      }
+    };
+    let input = input.and_then(|input| {
+      if input.trim().is_empty() {
+        Err("no input".to_owned())
+      } else {
+        Ok(input)
+      }
+    });
+    jobs.push((day, input));
+  }
+
+  let threads = match (args.sequential, args.jobs) {
+    (true, _) => 1,
+    (false, Some(jobs)) => jobs as usize,
+    (false, None) => pool::default_threads(),
+  };
+  let start = Instant::now();
+  let outcomes = pool::map(threads, &jobs, |(day, input)| {
+    let input = input.as_ref().ok()?;
+    // both parts share the parser, so the other part would fail the same way
+    let reports = parts
+      .iter()
+      .map(|&part| day.bench(part, input, &config))
+      .collect::<Result<Vec<_>, _>>();
+    Some(reports)
+  });
+  let elapsed = start.elapsed();
+
+  let mut summary = Summary::default();
+  let mut failures = vec![];
+  let mut missing = vec![];
+  let mut invalid = vec![];
+  let mut panicked = vec![];
+  for ((day, input), outcome) in jobs.iter().zip(outcomes) {
+    let (day, reports) = match (input, outcome) {
+      (Err(reason), _) => {
+        if json {
+          parts
+            .iter()
+            .for_each(|&part| emit(Record::failed(day, part, Status::Skipped, reason)));
+        } else {
+          println!("{day} has {reason}, skipping\n");
+        }
+        continue;
+      }
+      (Ok(_), Err(panic)) => {
+        if json {
+          parts
+            .iter()
+            .for_each(|&part| emit(Record::failed(day, part, Status::Panicked, &panic.0)));
+        } else {
+          eprintln!("error: {day} {panic}\n");
+        }
+        parts
+          .iter()
+          .for_each(|&part| summary.push_panicked(day, part));
+        panicked.push(day);
+        continue;
+      }
+      (Ok(_), Ok(Some(Ok(reports)))) => (*day, reports),
+      (Ok(_), Ok(Some(Err(e)))) => {
+        if json {
+          parts
+            .iter()
+            .for_each(|&part| emit(Record::failed(day, part, Status::Invalid, &e)));
+        } else {
+          eprintln!("error: {day} has invalid input: {e}\n");
+        }
+        invalid.push(day);
+        continue;
+      }
+      (Ok(_), Ok(None)) => unreachable!("every input is solved"),
+    };
+
+    // answers only match the input they were recorded for
+    let answers_path = match (&args.answers, &args.input) {
+      (Some(path), _) => Some(path.clone()),
+      (None, None) => Some(day.default_answers()),
+      (None, Some(_)) => None,
+    };
+    let mut answers = match &answers_path {
+      Some(path) => Answers::load(path)?,
+      None => Answers::default(),
+    };
+
+    for (&part, report) in parts.iter().zip(reports) {
+      let title = format!("{day} part {part}");
+      if json {
+        emit(Record::new(day, part, &report, answers.get(part)));
+      } else if args.bench {
+        print_answer(&title, &report.answer);
+        println!("  parse: {}", report.parse);
+        println!("  solve: {}", report.solve);
+        print_allocs(&report);
+        println!();
+      } else {
+        print_answer(&title, &report.answer);
+        let time = report.parse.median + report.solve.median;
+        println!("Done in {} microseconds", time.as_micros());
+        print_allocs(&report);
+        println!();
+      }
+      summary.push(day, part, &report);
+
+      let answer = report.answer;
+      match answers.get(part) {
+        Some(expected) if expected != answer => {
+          if !json {
+            println!("{title} answer changed, expected:\n{expected}\n");
+          }
+          failures.push((title, answers::diff(expected, &answer)));
+        }
+        None if args.check => missing.push(title),
+        _ => {}
+      }
+      answers.set(part, answer);
+    }
+
+    if args.save_answers {
+      if let Some(path) = &answers_path {
+        answers.save(path)?;
+      }
+    }
+  }
+
+  // a single day already printed everything there is to know
+  if !json && jobs.len() > 1 && !summary.is_empty() {
+    println!("{summary}\n");
+    println!(
+      "Ran {} day(s) on {threads} thread(s) in {elapsed:.2?}\n",
+      jobs.len()
+    );
+  }
+
+  if !invalid.is_empty() {
+    anyhow::bail!("{} day(s) had invalid input", invalid.len());
+  }
+  if !panicked.is_empty() {
+    anyhow::bail!("{} day(s) panicked", panicked.len());
+  }
+
+  if args.check {
+    // json records already say which answers are missing or changed
+    if !json {
+      for title in missing.iter() {
+        println!("{title} has no known answer");
+      }
+      for (title, diff) in failures.iter() {
+        println!("{title} answer changed:\n{diff}");
+      }
+    }
+    if !failures.is_empty() {
+      anyhow::bail!("{} answer(s) changed", failures.len());
+    }
+  }
+
+  Ok(())
+}
+
+fn print_allocs(report: &bench::Report) {
+  if let (Some(parse), Some(solve)) = (report.parse_allocs, report.solve_allocs) {
+    println!("  parse memory: {parse}");
+    println!("  solve memory: {solve}");
+  }
+}
+
+fn print_answer(title: &str, answer: &str) {
+  if answer.contains('\n') {
+    println!("{title} answer:\n{answer}");
+  } else {
+    println!("{title} answer: {answer}");
+  }
+}
@@ -0,0 +1,350 @@
+//! Helpers for parsing puzzle input, which report errors with the line and column they occurred at.
+//!
+//! Every helper works on a [`Text`], a piece of the puzzle input which remembers the whole input,
+//! so that the [`ParseError`] of a deeply nested helper still points at the right place:
+//!
+//! ```
+//! use aoc::parse::Text;
+//!
+//! let input = "a: 1, 2\nb: 3\n";
+//! let lines = Text::new(input)
+//!   .key_values(":")?
+//!   .into_iter()
+//!   .map(|(key, values)| Ok((key.as_str(), values.integers::<u32>(",")?)))
+//!   .collect::<Result<Vec<_>, aoc::ParseError>>()?;
+//! assert_eq!(lines, [("a", vec![1, 2]), ("b", vec![3])]);
+//!
+//! let error = Text::new("a: 1, x").key_values(":")?[0].1.integers::<u32>(",").unwrap_err();
+//! assert_eq!((error.line, error.column), (1, 7));
+//! # Ok::<(), aoc::ParseError>(())
+//! ```
+
+use std::fmt;
+use std::str::FromStr;
+
+use regex::{Captures, Regex};
+
+use crate::grid::Grid;
+use crate::ParseError;
+
+/// A piece of the puzzle input.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct Text<'a> {
+  source: &'a str,
+  text: &'a str,
+}
+
+impl<'a> Text<'a> {
+  /// The whole puzzle input.
+  pub fn new(source: &'a str) -> Self {
+    Self {
+      source,
+      text: source,
+    }
+  }
+
+  pub fn as_str(&self) -> &'a str {
+    self.text
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.text.is_empty()
+  }
+
+  /// Another piece of the same input. `text` must be a subslice of this one.
+  fn sub(&self, text: &'a str) -> Self {
+    Self {
+      source: self.source,
+      text,
+    }
+  }
+
+  /// An error pointing at this text.
+  pub fn error(&self, expected: impl Into<String>) -> ParseError {
+    ParseError::new(self.source, self.text, expected)
+  }
+
+  /// An error pointing just past the end of this text.
+  pub fn error_after(&self, expected: impl Into<String>) -> ParseError {
+    ParseError::after(self.source, self.text, expected)
+  }
+
+  pub fn trim(self) -> Self {
+    self.sub(self.text.trim())
+  }
+
+  pub fn lines(self) -> impl Iterator<Item = Text<'a>> {
+    self.text.lines().map(move |line| self.sub(line))
+  }
+
+  /// Groups of lines separated by blank lines. Leading and trailing blank lines are ignored.
+  pub fn blocks(self) -> impl Iterator<Item = Text<'a>> {
+    let mut lines = self.lines().peekable();
+    std::iter::from_fn(move || {
+      while lines.next_if(|line| line.text.trim().is_empty()).is_some() {}
+      let first = lines.next()?;
+      let mut last = first;
+      while let Some(line) = lines.next_if(|line| !line.text.trim().is_empty()) {
+        last = line;
+      }
+      let start = offset(self.text, first.text);
+      let end = offset(self.text, last.text) + last.text.len();
+      Some(self.sub(&self.text[start..end]))
+    })
+  }
+
+  pub fn split(self, separator: &'a str) -> impl Iterator<Item = Text<'a>> {
+    self.text.split(separator).map(move |s| self.sub(s))
+  }
+
+  /// Splits at the first `separator`, which must be present.
+  pub fn split_once(self, separator: &str) -> Result<(Text<'a>, Text<'a>), ParseError> {
+    let (a, b) = self
+      .text
+      .split_once(separator)
+      .ok_or_else(|| self.error_after(format!("`{separator}`")))?;
+    Ok((self.sub(a), self.sub(b)))
+  }
+
+  /// The rest of the text after `prefix`, which must be present.
+  pub fn strip_prefix(self, prefix: &str) -> Result<Text<'a>, ParseError> {
+    match self.text.strip_prefix(prefix) {
+      Some(rest) => Ok(self.sub(rest)),
+      None => {
+        let len = self
+          .text
+          .char_indices()
+          .nth(prefix.chars().count())
+          .map_or(self.text.len(), |(i, _)| i);
+        Err(self.sub(&self.text[..len]).error(format!("`{prefix}`")))
+      }
+    }
+  }
+
+  /// Parses the whole text as a `T`, reporting `expected` on failure.
+  pub fn parse<T: FromStr>(self, expected: &str) -> Result<T, ParseError> {
+    self.text.parse().map_err(|_| self.error(expected))
+  }
+
+  /// Parses the text as an integer, ignoring surrounding whitespace.
+  pub fn integer<T: FromStr>(self) -> Result<T, ParseError> {
+    self.trim().parse("an integer")
+  }
+
+  /// Parses each piece between `separator`s with `f`.
+  pub fn list<T>(
+    self,
+    separator: &'a str,
+    f: impl FnMut(Text<'a>) -> Result<T, ParseError>,
+  ) -> Result<Vec<T>, ParseError> {
+    self.split(separator).map(f).collect()
+  }
+
+  /// Integers separated by `separator`, with optional whitespace around each of them.
+  pub fn integers<T: FromStr>(self, separator: &'a str) -> Result<Vec<T>, ParseError> {
+    self.list(separator, Text::integer)
+  }
+
+  /// A line like `key: value`, split at `separator`, with whitespace trimmed from both sides.
+  pub fn key_value(self, separator: &str) -> Result<(Text<'a>, Text<'a>), ParseError> {
+    let (key, value) = self.split_once(separator)?;
+    Ok((key.trim(), value.trim()))
+  }
+
+  /// A [`Text::key_value`] for each non-blank line.
+  pub fn key_values(self, separator: &str) -> Result<Vec<(Text<'a>, Text<'a>)>, ParseError> {
+    self
+      .lines()
+      .filter(|line| !line.text.trim().is_empty())
+      .map(|line| line.key_value(separator))
+      .collect()
+  }
+
+  /// A grid with a cell for each character, where `f` rejects the characters that aren't `expected`.
+  pub fn grid<T>(
+    self,
+    expected: &str,
+    f: impl FnMut(char) -> Option<T>,
+  ) -> Result<Grid<T>, ParseError> {
+    Grid::try_parse(self.text, expected, f).map_err(|e| e.within(self.source, self.text))
+  }
+
+  /// Matches `regex` against the text, and parses its capture groups into a tuple.
+  ///
+  /// The regex should be anchored with `^` and `$` to reject trailing garbage.
+  pub fn captures<T: FromCaptures<'a>>(self, regex: &Regex) -> Result<T, ParseError> {
+    let captures = regex
+      .captures(self.text)
+      .ok_or_else(|| self.error(format!("text matching `{regex}`")))?;
+    T::from_captures(self, &captures)
+  }
+}
+
+fn offset(outer: &str, inner: &str) -> usize {
+  inner.as_ptr() as usize - outer.as_ptr() as usize
+}
+
+impl fmt::Debug for Text<'_> {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    fmt::Debug::fmt(self.text, f)
+  }
+}
+
+impl fmt::Display for Text<'_> {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    fmt::Display::fmt(self.text, f)
+  }
+}
+
+/// A value parsed from a piece of the input, e.g. a capture group.
+pub trait FromText<'a>: Sized {
+  fn from_text(text: Text<'a>) -> Result<Self, ParseError>;
+}
+
+impl<'a> FromText<'a> for Text<'a> {
+  fn from_text(text: Text<'a>) -> Result<Self, ParseError> {
+    Ok(text)
+  }
+}
+
+impl<'a, T: FromStr> FromText<'a> for T {
+  fn from_text(text: Text<'a>) -> Result<Self, ParseError> {
+    let name = std::any::type_name::<T>();
+    let name = name.rsplit("::").next().unwrap_or(name);
+    text.parse(&format!("a valid `{name}`"))
+  }
+}
+
+/// A tuple parsed from the capture groups of a regex, see [`Text::captures`].
+pub trait FromCaptures<'a>: Sized {
+  fn from_captures(text: Text<'a>, captures: &Captures<'a>) -> Result<Self, ParseError>;
+}
+
+macro_rules! impl_from_captures {
+  ($($n:literal: $t:ident),+) => {
+    impl<'a, $($t: FromText<'a>),+> FromCaptures<'a> for ($($t,)+) {
+      fn from_captures(text: Text<'a>, captures: &Captures<'a>) -> Result<Self, ParseError> {
+        Ok(($(
+          match captures.get($n) {
+            Some(group) => $t::from_text(text.sub(group.as_str()))?,
+            None => return Err(text.error(format!("a match for capture group {}", $n))),
+          },
+        )+))
+      }
+    }
+  };
+}
+
+impl_from_captures!(1: A);
+impl_from_captures!(1: A, 2: B);
+impl_from_captures!(1: A, 2: B, 3: C);
+impl_from_captures!(1: A, 2: B, 3: C, 4: D);
+impl_from_captures!(1: A, 2: B, 3: C, 4: D, 5: E);
+impl_from_captures!(1: A, 2: B, 3: C, 4: D, 5: E, 6: F);
+impl_from_captures!(1: A, 2: B, 3: C, 4: D, 5: E, 6: F, 7: G);
+impl_from_captures!(1: A, 2: B, 3: C, 4: D, 5: E, 6: F, 7: G, 8: H);
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn at(error: ParseError) -> (usize, usize, String) {
+    (error.line, error.column, error.found)
+  }
+
+  #[test]
+  fn integers() {
+    let text = Text::new("1, 2,3\n4,x5\n");
+    let mut lines = text.lines();
+    assert_eq!(
+      lines.next().unwrap().integers::<u8>(",").unwrap(),
+      [1, 2, 3]
+    );
+    let error = lines.next().unwrap().integers::<u8>(",").unwrap_err();
+    assert_eq!(at(error.clone()), (2, 3, "x5".into()));
+    assert_eq!(error.expected, "an integer");
+  }
+
+  #[test]
+  fn blocks() {
+    let text = Text::new("\n1\n2\n\n\n3\n  \n4\n5\n\n");
+    let blocks = text.blocks().map(|b| b.as_str()).collect::<Vec<_>>();
+    assert_eq!(blocks, ["1\n2", "3", "4\n5"]);
+
+    let block = text.blocks().nth(2).unwrap();
+    let error = block.lines().nth(1).unwrap().error("something else");
+    assert_eq!(at(error), (9, 1, "5".into()));
+    assert_eq!(Text::new("").blocks().count(), 0);
+  }
+
+  #[test]
+  fn key_values() {
+    let text = Text::new("Time: 7\n\nDistance:9\nSpeed 3");
+    let error = text.key_values(":").unwrap_err();
+    assert_eq!(at(error.clone()), (4, 8, "".into()));
+    assert_eq!(error.expected, "`:`");
+
+    let (key, value) = text.lines().nth(2).unwrap().key_value(":").unwrap();
+    assert_eq!(
+      (key.as_str(), value.integer::<u32>().unwrap()),
+      ("Distance", 9)
+    );
+  }
+
+  #[test]
+  fn strip_prefix() {
+    let text = Text::new("Monkey 0:\nMonkee 1:");
+    let mut lines = text.lines();
+    assert_eq!(
+      lines
+        .next()
+        .unwrap()
+        .strip_prefix("Monkey ")
+        .unwrap()
+        .as_str(),
+      "0:"
+    );
+    let error = lines.next().unwrap().strip_prefix("Monkey ").unwrap_err();
+    assert_eq!(at(error), (2, 1, "Monkee ".into()));
+  }
+
+  #[test]
+  fn grid() {
+    let text = Text::new("header\n\n.#\n#.\n");
+    let grid = text.blocks().nth(1).unwrap();
+    assert_eq!(
+      grid.grid("`.` or `#`", |c| Some(c == '#')).unwrap().cols(),
+      2
+    );
+
+    let text = Text::new("header\n\n.#\n#?\n");
+    let grid = text.blocks().nth(1).unwrap();
+    let error = grid.grid("`.` or `#`", |c| Some(c == '#').filter(|_| c != '?'));
+    assert_eq!(at(error.unwrap_err()), (4, 2, "?".into()));
+  }
+
+  #[test]
+  fn captures() {
+    let re = Regex::new(r"^move (\d+) from (\d+) to (\w+)$").unwrap();
+    let text = Text::new("move 1 from 2 to 3\nmove 300 from 2 to a\nmove 1 to 2");
+    let mut lines = text.lines();
+
+    let (n, from, to) = lines
+      .next()
+      .unwrap()
+      .captures::<(u8, u8, Text)>(&re)
+      .unwrap();
+    assert_eq!((n, from, to.as_str()), (1, 2, "3"));
+
+    let error = lines
+      .next()
+      .unwrap()
+      .captures::<(u8, u8, u8)>(&re)
+      .unwrap_err();
+    assert_eq!(at(error.clone()), (2, 6, "300".into()));
+    assert_eq!(error.expected, "a valid `u8`");
+
+    let error = lines.next().unwrap().captures::<(u8, u8)>(&re).unwrap_err();
+    assert_eq!(at(error), (3, 1, "move 1 to 2".into()));
+  }
+}
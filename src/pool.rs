@@ -0,0 +1,129 @@
+//! A fixed-size pool of worker threads for running independent jobs.
+
+use std::any::Any;
+use std::fmt;
+use std::num::NonZeroUsize;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
+
+/// One thread per core, or a single thread if that can't be determined.
+pub fn default_threads() -> usize {
+  thread::available_parallelism().map_or(1, NonZeroUsize::get)
+}
+
+/// A job which panicked, with the panic's message.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Panic(pub String);
+
+impl Panic {
+  fn new(payload: Box<dyn Any + Send>) -> Self {
+    let message = match payload.downcast::<String>() {
+      Ok(message) => *message,
+      Err(payload) => match payload.downcast::<&str>() {
+        Ok(message) => message.to_string(),
+        Err(_) => "unknown panic".to_owned(),
+      },
+    };
+    Self(message)
+  }
+}
+
+impl fmt::Display for Panic {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "panicked: {}", self.0)
+  }
+}
+
+/// Calls `f` on every item using up to `threads` worker threads, and returns the results in the
+/// same order as `items`. With a single thread, everything runs on the calling thread.
+///
+/// A job that panics doesn't stop the others, and its result is the [`Panic`].
+pub fn map<T, R, F>(threads: usize, items: &[T], f: F) -> Vec<Result<R, Panic>>
+where
+  T: Sync,
+  R: Send,
+  F: Fn(&T) -> R + Sync,
+{
+  let run = |item| panic::catch_unwind(AssertUnwindSafe(|| f(item))).map_err(Panic::new);
+  let threads = threads.clamp(1, items.len().max(1));
+  if threads == 1 {
+    return items.iter().map(run).collect();
+  }
+
+  // workers take the next item as soon as they're done, so one slow job doesn't hold up the rest
+  let next = AtomicUsize::new(0);
+  let results = Mutex::new((0..items.len()).map(|_| None).collect::<Vec<_>>());
+  thread::scope(|scope| {
+    for _ in 0..threads {
+      scope.spawn(|| loop {
+        let i = next.fetch_add(1, Ordering::Relaxed);
+        let Some(item) = items.get(i) else {
+          break;
+        };
+        let result = run(item);
+        results.lock().unwrap()[i] = Some(result);
+      });
+    }
+  });
+
+  results
+    .into_inner()
+    .unwrap()
+    .into_iter()
+    .map(|result| result.unwrap())
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+  use std::collections::HashSet;
+  use std::time::Duration;
+
+  use super::*;
+
+  #[test]
+  fn keeps_order() {
+    let items = (0..50u64).collect::<Vec<_>>();
+    let squares = map(4, &items, |&n| {
+      // finish out of order
+      thread::sleep(Duration::from_micros(50 - n));
+      n * n
+    });
+    assert_eq!(squares, items.iter().map(|n| Ok(n * n)).collect::<Vec<_>>());
+    assert_eq!(map(4, &[] as &[u64], |&n| n), []);
+  }
+
+  #[test]
+  fn uses_the_threads() {
+    let items = (0..8).collect::<Vec<_>>();
+    let ids = |threads| {
+      map(threads, &items, |_| {
+        thread::sleep(Duration::from_millis(5));
+        thread::current().id()
+      })
+      .into_iter()
+      .map(Result::unwrap)
+      .collect::<HashSet<_>>()
+    };
+    assert!(ids(4).len() > 1);
+    assert_eq!(ids(1), [thread::current().id()].into_iter().collect());
+  }
+
+  #[test]
+  fn catches_panics() {
+    let items = (0..8u64).collect::<Vec<_>>();
+    for threads in [1, 4] {
+      let results = map(threads, &items, |&n| {
+        if n == 3 {
+          panic!("no {n}");
+        }
+        n
+      });
+      assert_eq!(results[3], Err(Panic("no 3".to_owned())));
+      assert_eq!(results[7], Ok(7));
+      assert_eq!(results.iter().filter(|r| r.is_ok()).count(), 7);
+    }
+  }
+}
@@ -0,0 +1,118 @@
+//! Machine-readable results, emitted by `--format json` as one JSON object per line.
+//!
+//! ```json
+//! {"day":6,"name":"day6","part":1,"status":"ok","answer":"1480","parse":{...},"solve":{...}}
+//! ```
+
+use serde::Serialize;
+
+use crate::alloc::Allocs;
+use crate::bench::{Report, Stats};
+use crate::days::Day;
+
+#[derive(Clone, Debug, Serialize)]
+pub struct Record {
+  pub day: u32,
+  pub name: &'static str,
+  pub part: u8,
+  pub status: Status,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub answer: Option<String>,
+  /// The known answer, only present if the answer changed.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub expected: Option<String>,
+  /// Why the part didn't run.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub error: Option<String>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub parse: Option<Timing>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub solve: Option<Timing>,
+  /// Only present with the `count-allocs` feature.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub parse_allocs: Option<Allocs>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub solve_allocs: Option<Allocs>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Status {
+  /// The answer matches the known answer.
+  Ok,
+  /// There is no known answer to compare against.
+  Unknown,
+  /// The answer differs from the known answer.
+  Changed,
+  /// The input failed to parse.
+  Invalid,
+  /// Solving panicked.
+  Panicked,
+  /// There was no input to run on.
+  Skipped,
+}
+
+/// Durations in nanoseconds.
+#[derive(Clone, Copy, Debug, Serialize)]
+pub struct Timing {
+  pub runs: usize,
+  pub min_ns: u64,
+  pub median_ns: u64,
+  pub mean_ns: u64,
+  pub p95_ns: u64,
+}
+
+impl Record {
+  /// A record for a part which didn't produce an answer.
+  pub fn failed(day: &Day, part: u8, status: Status, error: impl ToString) -> Self {
+    Self {
+      day: day.number,
+      name: day.name,
+      part,
+      status,
+      answer: None,
+      expected: None,
+      error: Some(error.to_string()),
+      parse: None,
+      solve: None,
+      parse_allocs: None,
+      solve_allocs: None,
+    }
+  }
+
+  /// A record for a part which ran, compared against the `expected` answer if there is one.
+  pub fn new(day: &Day, part: u8, report: &Report, expected: Option<&str>) -> Self {
+    let status = match expected {
+      None => Status::Unknown,
+      Some(expected) if expected == report.answer => Status::Ok,
+      Some(_) => Status::Changed,
+    };
+
+    Self {
+      day: day.number,
+      name: day.name,
+      part,
+      status,
+      answer: Some(report.answer.clone()),
+      expected: (status == Status::Changed).then(|| expected.unwrap().to_owned()),
+      error: None,
+      parse: Some(report.parse.into()),
+      solve: Some(report.solve.into()),
+      parse_allocs: report.parse_allocs,
+      solve_allocs: report.solve_allocs,
+    }
+  }
+}
+
+impl From<Stats> for Timing {
+  fn from(stats: Stats) -> Self {
+    let ns = |d: std::time::Duration| d.as_nanos() as u64;
+    Self {
+      runs: stats.runs,
+      min_ns: ns(stats.min),
+      median_ns: ns(stats.median),
+      mean_ns: ns(stats.mean),
+      p95_ns: ns(stats.p95),
+    }
+  }
+}
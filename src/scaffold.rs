@@ -0,0 +1,228 @@
+//! Generates the module for a new day from `templates/day.rs`, and registers it in `src/days/mod.rs`.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::Context;
+
+const TEMPLATE: &str = include_str!("../templates/day.rs");
+
+pub fn render(day: u32) -> String {
+  TEMPLATE.replace("{{day}}", &day.to_string())
+}
+
+/// Adds `pub mod day{day};` and the day's entry in `DAYS` to the source of `src/days/mod.rs`.
+///
+/// Module declarations stay in the order rustfmt sorts them, and `DAYS` stays ordered by day.
+pub fn register(registry: &str, day: u32) -> anyhow::Result<String> {
+  let name = format!("day{day}");
+  let module = format!("pub mod {name};");
+  let entry = format!("  Day::new::<day{day}::Day{day}>({day}, \"day{day}\"),");
+
+  let mut lines = registry.lines().collect::<Vec<_>>();
+  if lines.iter().any(|line| *line == module) {
+    anyhow::bail!("day {day} is already registered");
+  }
+
+  let modules = lines
+    .iter()
+    .enumerate()
+    .filter_map(|(i, line)| Some((i, line.strip_prefix("pub mod ")?.strip_suffix(';')?)))
+    .collect::<Vec<_>>();
+  let Some(&(last, _)) = modules.last() else {
+    anyhow::bail!("no `pub mod` declarations in the registry");
+  };
+  let at = modules
+    .iter()
+    .find(|(_, module)| *module > name.as_str())
+    .map_or(last + 1, |(i, _)| *i);
+  lines.insert(at, &module);
+
+  let Some(start) = lines
+    .iter()
+    .position(|line| line.starts_with("pub static DAYS"))
+  else {
+    anyhow::bail!("no `DAYS` table in the registry");
+  };
+  let Some(end) = lines[start..].iter().position(|line| *line == "];") else {
+    anyhow::bail!("the `DAYS` table is not closed");
+  };
+  let end = start + end;
+  let at = (start + 1..end)
+    .find(|&i| entry_day(lines[i]).is_some_and(|n| n > day))
+    .unwrap_or(end);
+  lines.insert(at, &entry);
+
+  let mut registry = lines.join("\n");
+  registry.push('\n');
+  Ok(registry)
+}
+
+/// The day number of a `DAYS` entry like `Day::new::<day7::Day7>(7, "day7"),`.
+fn entry_day(line: &str) -> Option<u32> {
+  let (_, rest) = line.split_once(">(")?;
+  let (number, _) = rest.split_once(',')?;
+  number.trim().parse().ok()
+}
+
+/// What [`create`] wrote.
+#[derive(Debug)]
+pub struct Created {
+  pub module: PathBuf,
+  pub registry: PathBuf,
+}
+
+/// Creates `day{day}/mod.rs` in `days_dir` along with empty `input.txt` and `example.txt` files,
+/// and registers the day in `days_dir/mod.rs`.
+///
+/// An input that was already fetched is kept.
+pub fn create(days_dir: &Path, day: u32) -> anyhow::Result<Created> {
+  let dir = days_dir.join(format!("day{day}"));
+  let module = dir.join("mod.rs");
+  if module.exists() {
+    anyhow::bail!("day {day} already exists at `{}`", module.display());
+  }
+
+  let registry = days_dir.join("mod.rs");
+  let source = fs::read_to_string(&registry)
+    .with_context(|| format!("failed to read `{}`", registry.display()))?;
+  let source = register(&source, day)?;
+
+  fs::create_dir_all(&dir).with_context(|| format!("failed to create `{}`", dir.display()))?;
+  for name in ["input.txt", "example.txt"] {
+    let path = dir.join(name);
+    if !path.exists() {
+      fs::write(&path, "").with_context(|| format!("failed to write `{}`", path.display()))?;
+    }
+  }
+  fs::write(&module, render(day))
+    .with_context(|| format!("failed to write `{}`", module.display()))?;
+  fs::write(&registry, source)
+    .with_context(|| format!("failed to write `{}`", registry.display()))?;
+
+  Ok(Created { module, registry })
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::days::{Day, DAYS};
+
+  const REGISTRY: &str = r#"use crate::Solution;
+
+pub mod day1;
+pub mod day13;
+pub mod day13_bump;
+pub mod day14;
+pub mod day2;
+
+pub static DAYS: &[Day] = &[
+  Day::new::<day1::Day1>(1, "day1"),
+  Day::new::<day2::Day2>(2, "day2"),
+  Day::new::<day13::Day13>(13, "day13"),
+  Day::new::<day13_bump::Day13Bump>(13, "day13-bump"),
+  Day::new::<day14::Day14>(14, "day14"),
+];
+"#;
+
+  #[test]
+  fn renders_the_day() {
+    let source = render(15);
+    assert!(source.contains("pub struct Day15;"));
+    assert!(source.contains("impl Solution for Day15 {"));
+    assert!(!source.contains("{{"));
+  }
+
+  #[test]
+  fn registers_in_order() {
+    let registry = register(REGISTRY, 15).unwrap();
+    let lines = registry.lines().collect::<Vec<_>>();
+
+    let module = lines.iter().position(|l| *l == "pub mod day15;").unwrap();
+    assert_eq!(lines[module - 1], "pub mod day14;");
+    assert_eq!(lines[module + 1], "pub mod day2;");
+
+    let entry = lines
+      .iter()
+      .position(|l| l.contains("Day::new::<day15::Day15>(15, \"day15\")"))
+      .unwrap();
+    assert!(lines[entry - 1].contains("(14, \"day14\")"));
+    assert_eq!(lines[entry + 1], "];");
+
+    assert!(register(&registry, 15).is_err());
+    assert!(register(REGISTRY, 2).is_err());
+
+    // between `day1` and `day2`, and after every variant of day 13
+    let registry = register(REGISTRY, 10).unwrap();
+    assert!(registry.contains("pub mod day1;\npub mod day10;\npub mod day13;"));
+    assert!(registry.contains("(2, \"day2\"),\n  Day::new::<day10::Day10>(10, \"day10\"),"));
+    let registry = register(REGISTRY, 20).unwrap();
+    assert!(registry.contains("pub mod day2;\npub mod day20;\n\n"));
+    assert!(registry.contains("(14, \"day14\"),\n  Day::new::<day20::Day20>(20, \"day20\"),\n];"));
+  }
+
+  /// What `templates/day.rs` renders to, which can't be compiled from here.
+  struct Template;
+
+  impl crate::Solution for Template {
+    type Input<'a> = Vec<&'a str>;
+    type Output1 = usize;
+    type Output2 = usize;
+
+    fn parse(source: &str) -> Result<Self::Input<'_>, crate::ParseError> {
+      Ok(source.lines().collect())
+    }
+
+    fn part1(_input: &Self::Input<'_>) -> Self::Output1 {
+      0
+    }
+
+    fn part2(_input: &Self::Input<'_>) -> Self::Output2 {
+      0
+    }
+  }
+
+  #[test]
+  fn new_days_pass_the_tests() {
+    // the template doesn't need a generator, and must stay in sync with `Template`
+    let template = render(15);
+    assert!(!template.contains("fn generate"));
+    assert!(template.contains("type Input<'a> = Vec<&'a str>;"));
+
+    // the next day that `aoc new` would create
+    if let Some(n) = (1..=25).find(|&n| DAYS.iter().all(|day| day.number != n)) {
+      let registry = register(include_str!("days/mod.rs"), n).unwrap();
+      assert!(registry.contains(&format!("  Day::new::<day{n}::Day{n}>({n}, \"day{n}\"),\n")));
+    }
+
+    let day = Day::new::<Template>(15, "day15");
+    assert_eq!(day.generate(10, 0), None);
+    crate::days::tests::check_generator(&day);
+  }
+
+  #[test]
+  fn creates_the_day() {
+    let dir = std::env::temp_dir().join(format!("aoc-{}-scaffold", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(dir.join("day15")).unwrap();
+    fs::write(dir.join("mod.rs"), REGISTRY).unwrap();
+    fs::write(dir.join("day15/input.txt"), "1\n2\n").unwrap();
+
+    let created = create(&dir, 15).unwrap();
+    assert_eq!(fs::read_to_string(created.module).unwrap(), render(15));
+    assert!(fs::read_to_string(created.registry)
+      .unwrap()
+      .contains("pub mod day15;"));
+    // the fetched input is kept
+    assert_eq!(
+      fs::read_to_string(dir.join("day15/input.txt")).unwrap(),
+      "1\n2\n"
+    );
+    assert_eq!(
+      fs::read_to_string(dir.join("day15/example.txt")).unwrap(),
+      ""
+    );
+
+    assert!(create(&dir, 15).is_err());
+  }
+}
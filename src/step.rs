@@ -0,0 +1,280 @@
+//! Simulations that can be run one step at a time, and an interactive prompt for debugging them:
+//!
+//! ```text
+//! $ cargo run --release -- debug 10 --part 1
+//! step 0: cycle=1 x=1 signal=0
+//! > until cycle == 20
+//! step 19: cycle=20 x=21 signal=0
+//! > print
+//! ```
+//!
+//! Days 5, 9, 10, 11 and 14 have a `stepper` function which starts their simulation.
+
+use std::fmt;
+use std::io::{self, BufRead, Write};
+use std::str::FromStr;
+
+/// A simulation which can be advanced one step at a time. What a step is depends on the day,
+/// e.g. a clock cycle for day 10 or a round of monkeys throwing items for day 11.
+///
+/// [`Display`](fmt::Display) shows the whole state, e.g. a grid.
+pub trait Stepper: fmt::Display {
+  /// Takes one step, or returns `false` if the simulation is already over.
+  fn step(&mut self) -> bool;
+  /// Named numbers describing the current state, which [`Condition`]s can refer to.
+  fn vars(&self) -> Vec<(String, i64)>;
+}
+
+/// Stops running, e.g. `x >= 20`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Condition {
+  pub var: String,
+  pub cmp: Cmp,
+  pub value: i64,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Cmp {
+  Eq,
+  Ne,
+  Lt,
+  Le,
+  Gt,
+  Ge,
+}
+
+impl Condition {
+  /// Whether the condition holds for `vars`, or `None` if it refers to a variable that isn't
+  /// there.
+  pub fn holds(&self, vars: &[(String, i64)]) -> Option<bool> {
+    let (_, value) = vars.iter().find(|(name, _)| *name == self.var)?;
+    Some(match self.cmp {
+      Cmp::Eq => *value == self.value,
+      Cmp::Ne => *value != self.value,
+      Cmp::Lt => *value < self.value,
+      Cmp::Le => *value <= self.value,
+      Cmp::Gt => *value > self.value,
+      Cmp::Ge => *value >= self.value,
+    })
+  }
+}
+
+impl FromStr for Condition {
+  type Err = String;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    let [var, cmp, value] = s.split_whitespace().collect::<Vec<_>>()[..] else {
+      return Err(format!("expected a condition like `x >= 20`, found `{s}`"));
+    };
+    let cmp = match cmp {
+      "==" => Cmp::Eq,
+      "!=" => Cmp::Ne,
+      "<" => Cmp::Lt,
+      "<=" => Cmp::Le,
+      ">" => Cmp::Gt,
+      ">=" => Cmp::Ge,
+      _ => return Err(format!("unknown comparison `{cmp}`")),
+    };
+    let value = value
+      .parse()
+      .map_err(|_| format!("expected an integer, found `{value}`"))?;
+    Ok(Condition {
+      var: var.to_owned(),
+      cmp,
+      value,
+    })
+  }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Command {
+  Run(u64),
+  Until(Condition),
+  Print,
+  Help,
+  Quit,
+}
+
+const HELP: &str = "\
+Commands:
+  step, s, <enter>        take one step
+  run <n>, r <n>          take up to n steps
+  until <cond>, u <cond>  take steps until e.g. `x >= 20`, using the variables shown
+                          after each command or `step`, the number of steps taken
+  print, p                print the whole state
+  help, h                 print this message
+  quit, q                 stop debugging";
+
+impl FromStr for Command {
+  type Err = String;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    let (name, args) = s.split_once(' ').unwrap_or((s, ""));
+    let args = args.trim();
+    match name {
+      "" | "s" | "step" if args.is_empty() => Ok(Command::Run(1)),
+      "r" | "run" => args
+        .parse()
+        .map(Command::Run)
+        .map_err(|_| format!("expected a number of steps, found `{args}`")),
+      "u" | "until" => Ok(Command::Until(args.parse()?)),
+      "p" | "print" if args.is_empty() => Ok(Command::Print),
+      "h" | "help" | "?" if args.is_empty() => Ok(Command::Help),
+      "q" | "quit" if args.is_empty() => Ok(Command::Quit),
+      _ => Err(format!("unknown command `{s}`, try `help`")),
+    }
+  }
+}
+
+/// Reads commands from `input` until it ends or says `quit`, and writes what happens to `output`.
+pub fn repl(
+  stepper: &mut dyn Stepper,
+  mut input: impl BufRead,
+  mut output: impl Write,
+) -> io::Result<()> {
+  let mut steps = 0u64;
+  let vars = |stepper: &dyn Stepper, steps: u64| {
+    let mut vars = vec![("step".to_owned(), steps as i64)];
+    vars.extend(stepper.vars());
+    vars
+  };
+  let status = |output: &mut dyn Write, stepper: &dyn Stepper, steps| {
+    let vars = vars(stepper, steps)[1..]
+      .iter()
+      .map(|(name, value)| format!("{name}={value}"))
+      .collect::<Vec<_>>();
+    writeln!(output, "step {steps}: {}", vars.join(" "))
+  };
+
+  status(&mut output, stepper, steps)?;
+  let mut line = String::new();
+  loop {
+    write!(output, "> ")?;
+    output.flush()?;
+    line.clear();
+    if input.read_line(&mut line)? == 0 {
+      writeln!(output)?;
+      return Ok(());
+    }
+
+    let command = match line.trim().parse::<Command>() {
+      Ok(command) => command,
+      Err(e) => {
+        writeln!(output, "{e}")?;
+        continue;
+      }
+    };
+    let done = match command {
+      Command::Run(n) => {
+        let mut done = false;
+        for _ in 0..n {
+          if !stepper.step() {
+            done = true;
+            break;
+          }
+          steps += 1;
+        }
+        done
+      }
+      Command::Until(condition) => {
+        if condition.holds(&vars(stepper, steps)).is_none() {
+          writeln!(output, "unknown variable `{}`", condition.var)?;
+          continue;
+        }
+        loop {
+          if condition.holds(&vars(stepper, steps)) == Some(true) {
+            break false;
+          }
+          if !stepper.step() {
+            break true;
+          }
+          steps += 1;
+        }
+      }
+      Command::Print => {
+        writeln!(output, "{stepper}")?;
+        continue;
+      }
+      Command::Help => {
+        writeln!(output, "{HELP}")?;
+        continue;
+      }
+      Command::Quit => return Ok(()),
+    };
+    if done {
+      writeln!(output, "the simulation is over")?;
+    }
+    status(&mut output, stepper, steps)?;
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  /// Counts up to `end`.
+  struct Counter {
+    n: i64,
+    end: i64,
+  }
+
+  impl Stepper for Counter {
+    fn step(&mut self) -> bool {
+      if self.n == self.end {
+        return false;
+      }
+      self.n += 1;
+      true
+    }
+
+    fn vars(&self) -> Vec<(String, i64)> {
+      vec![("n".to_owned(), self.n * 10)]
+    }
+  }
+
+  impl fmt::Display for Counter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+      write!(f, "counter at {}", self.n)
+    }
+  }
+
+  fn session(commands: &str) -> String {
+    let mut counter = Counter { n: 0, end: 10 };
+    let mut output = vec![];
+    repl(&mut counter, commands.as_bytes(), &mut output).unwrap();
+    String::from_utf8(output).unwrap()
+  }
+
+  #[test]
+  fn commands() {
+    assert_eq!(
+      session("\nrun 3\nuntil n >= 70\nuntil step == 8\np\nr 5\nq\nstep\n"),
+      "\
+step 0: n=0
+> step 1: n=10
+> step 4: n=40
+> step 7: n=70
+> step 8: n=80
+> counter at 8
+> the simulation is over
+step 10: n=100
+> "
+    );
+  }
+
+  #[test]
+  fn errors() {
+    let output = session("until y > 1\nrun x\njump\nuntil n => 1");
+    assert_eq!(
+      output.lines().collect::<Vec<_>>(),
+      [
+        "step 0: n=0",
+        "> unknown variable `y`",
+        "> expected a number of steps, found `x`",
+        "> unknown command `jump`, try `help`",
+        "> unknown comparison `=>`",
+        "> ",
+      ]
+    );
+  }
+}
@@ -0,0 +1,157 @@
+//! A table of every part that ran, with its answer and timings, and totals at the bottom.
+//!
+//! ```text
+//! Day     Part  Answer          Parse      Solve      Total
+//! Day 1      1  69289         26.10µs     1.20µs    27.30µs
+//! Day 10     2  (6 lines)      4.00µs     9.90µs    13.90µs
+//! Day 12     1  (panicked)          -          -          -
+//! Total                       30.10µs    11.10µs    41.20µs
+//! ```
+
+use std::fmt;
+use std::time::Duration;
+
+use crate::bench::Report;
+use crate::days::Day;
+
+#[derive(Clone, Debug)]
+pub struct Row {
+  pub day: String,
+  pub part: u8,
+  pub answer: String,
+  /// `None` for a part which panicked.
+  pub parse: Option<Duration>,
+  pub solve: Option<Duration>,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct Summary {
+  rows: Vec<Row>,
+}
+
+impl Summary {
+  /// Adds a part, using the median timings of `report`.
+  pub fn push(&mut self, day: &Day, part: u8, report: &Report) {
+    self.rows.push(Row {
+      day: day.to_string(),
+      part,
+      answer: report.answer.clone(),
+      parse: Some(report.parse.median),
+      solve: Some(report.solve.median),
+    });
+  }
+
+  /// Adds a part which panicked, and so has no answer or timings.
+  pub fn push_panicked(&mut self, day: &Day, part: u8) {
+    self.rows.push(Row {
+      day: day.to_string(),
+      part,
+      answer: "(panicked)".to_owned(),
+      parse: None,
+      solve: None,
+    });
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.rows.is_empty()
+  }
+
+  pub fn total(&self) -> Duration {
+    self.parse() + self.solve()
+  }
+
+  fn parse(&self) -> Duration {
+    self.rows.iter().filter_map(|row| row.parse).sum()
+  }
+
+  fn solve(&self) -> Duration {
+    self.rows.iter().filter_map(|row| row.solve).sum()
+  }
+}
+
+impl fmt::Display for Summary {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let answers = self
+      .rows
+      .iter()
+      .map(|row| match row.answer.lines().count() {
+        0 | 1 => row.answer.clone(),
+        n => format!("({n} lines)"),
+      })
+      .collect::<Vec<_>>();
+    let day_width = self.rows.iter().map(|row| row.day.len()).max().unwrap_or(0);
+    let day_width = day_width.max("Total".len());
+    let answer_width = answers.iter().map(String::len).max().unwrap_or(0);
+    let answer_width = answer_width.max("Answer".len());
+    let time = |d: Option<Duration>| match d {
+      Some(d) => format!("{d:.2?}"),
+      None => "-".to_owned(),
+    };
+
+    writeln!(
+      f,
+      "{:day_width$}  Part  {:answer_width$}  {:>9}  {:>9}  {:>9}",
+      "Day", "Answer", "Parse", "Solve", "Total"
+    )?;
+    for (row, answer) in self.rows.iter().zip(answers.iter()) {
+      writeln!(
+        f,
+        "{:day_width$}  {:>4}  {:answer_width$}  {:>9}  {:>9}  {:>9}",
+        row.day,
+        row.part,
+        answer,
+        time(row.parse),
+        time(row.solve),
+        time(row.parse.zip(row.solve).map(|(parse, solve)| parse + solve))
+      )?;
+    }
+    write!(
+      f,
+      "{:day_width$}  {:4}  {:answer_width$}  {:>9}  {:>9}  {:>9}",
+      "Total",
+      "",
+      "",
+      time(Some(self.parse())),
+      time(Some(self.solve())),
+      time(Some(self.total()))
+    )
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn table() {
+    let row = |day: &str, part, answer: &str, parse, solve| Row {
+      day: day.into(),
+      part,
+      answer: answer.into(),
+      parse: Some(Duration::from_micros(parse)),
+      solve: Some(Duration::from_micros(solve)),
+    };
+    let summary = Summary {
+      rows: vec![
+        row("Day 1", 1, "69289", 26, 1),
+        row("Day 10", 2, "#..\n.#.\n..#", 4, 10),
+        Row {
+          parse: None,
+          solve: None,
+          ..row("Day 12", 1, "(panicked)", 0, 0)
+        },
+      ],
+    };
+
+    assert_eq!(summary.total(), Duration::from_micros(41));
+    assert_eq!(
+      summary.to_string(),
+      "\
+Day     Part  Answer          Parse      Solve      Total
+Day 1      1  69289         26.00µs     1.00µs    27.00µs
+Day 10     2  (3 lines)      4.00µs    10.00µs    14.00µs
+Day 12     1  (panicked)          -          -          -
+Total                       30.00µs    11.00µs    41.00µs"
+    );
+  }
+}
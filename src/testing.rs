@@ -0,0 +1,43 @@
+//! Helpers for property tests.
+
+use proptest::prelude::*;
+
+#[derive(Clone, Debug)]
+enum Edit {
+  Insert(usize, char),
+  Remove(usize),
+  Replace(usize, char),
+}
+
+/// Strategy producing `base` with a few random characters inserted, removed or replaced.
+///
+/// Inserted characters are biased towards the ones that appear in puzzle inputs.
+pub fn mutated(base: &'static str) -> impl Strategy<Value = String> {
+  let ch = prop_oneof![
+    4 => proptest::sample::select(&b"0123456789[],- \n$:abcxyz"[..]).prop_map(char::from),
+    1 => any::<char>(),
+  ];
+  let edit = prop_oneof![
+    (any::<usize>(), ch.clone()).prop_map(|(i, c)| Edit::Insert(i, c)),
+    any::<usize>().prop_map(Edit::Remove),
+    (any::<usize>(), ch).prop_map(|(i, c)| Edit::Replace(i, c)),
+  ];
+
+  proptest::collection::vec(edit, 1..8).prop_map(move |edits| {
+    let mut s = base.chars().collect::<Vec<_>>();
+    for edit in edits {
+      match edit {
+        Edit::Insert(i, c) => s.insert(i % (s.len() + 1), c),
+        Edit::Remove(_) | Edit::Replace(..) if s.is_empty() => {}
+        Edit::Remove(i) => {
+          s.remove(i % s.len());
+        }
+        Edit::Replace(i, c) => {
+          let i = i % s.len();
+          s[i] = c;
+        }
+      }
+    }
+    s.into_iter().collect()
+  })
+}
@@ -0,0 +1,364 @@
+//! Renders grids as plain text, ANSI-coloured text or PPM/PNG images, and records animations
+//! as numbered frames on disk.
+//!
+//! Days turn their state into an [`Image`] of [`Pixel`]s, each with a glyph for text output and a
+//! colour for everything else, and the image takes care of the format.
+
+use std::fmt::{self, Write as _};
+use std::fs;
+use std::io::Write as _;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use anyhow::Context;
+use flate2::write::ZlibEncoder;
+use flate2::Compression;
+
+use crate::grid::Grid;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Rgb(pub u8, pub u8, pub u8);
+
+impl Rgb {
+  pub const BLACK: Rgb = Rgb(0, 0, 0);
+  pub const WHITE: Rgb = Rgb(255, 255, 255);
+  pub const GRAY: Rgb = Rgb(128, 128, 128);
+  pub const RED: Rgb = Rgb(220, 50, 47);
+  pub const GREEN: Rgb = Rgb(133, 153, 0);
+  pub const BLUE: Rgb = Rgb(38, 139, 210);
+  pub const YELLOW: Rgb = Rgb(181, 137, 0);
+  pub const ORANGE: Rgb = Rgb(203, 75, 22);
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Pixel {
+  /// Used by text output.
+  pub glyph: char,
+  /// Used by ANSI and image output.
+  pub color: Rgb,
+}
+
+impl Pixel {
+  pub const fn new(glyph: char, color: Rgb) -> Self {
+    Self { glyph, color }
+  }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Image {
+  pixels: Grid<Pixel>,
+}
+
+impl Image {
+  pub fn new(pixels: Grid<Pixel>) -> Self {
+    Self { pixels }
+  }
+
+  /// An image with a pixel for each cell of `grid`.
+  pub fn from_grid<T>(grid: &Grid<T>, mut f: impl FnMut(&T) -> Pixel) -> Self {
+    Self::new(grid.map(|cell| f(cell)))
+  }
+
+  /// An image of `cols` by `rows` pixels, where `f` is called with the `(x, y)` of every pixel.
+  pub fn from_fn(cols: usize, rows: usize, mut f: impl FnMut(usize, usize) -> Pixel) -> Self {
+    let mut pixels = Vec::with_capacity(cols * rows);
+    for y in 0..rows {
+      for x in 0..cols {
+        pixels.push(f(x, y));
+      }
+    }
+    Self::new(Grid::from_vec(pixels, cols))
+  }
+
+  pub fn width(&self) -> usize {
+    self.pixels.cols()
+  }
+
+  pub fn height(&self) -> usize {
+    self.pixels.rows()
+  }
+
+  /// The glyphs, one line per row, without a trailing newline.
+  pub fn ascii(&self) -> String {
+    let mut s = String::with_capacity((self.width() + 1) * self.height());
+    for y in 0..self.height() {
+      if y > 0 {
+        s.push('\n');
+      }
+      s.extend(self.pixels.row(y).map(|p| p.glyph));
+    }
+    s
+  }
+
+  /// Like [`Image::ascii`], with each glyph in its colour using 24-bit ANSI escapes.
+  pub fn ansi(&self) -> String {
+    let mut s = String::new();
+    for y in 0..self.height() {
+      if y > 0 {
+        s.push('\n');
+      }
+      let mut current = None;
+      for pixel in self.pixels.row(y) {
+        if current != Some(pixel.color) {
+          let Rgb(r, g, b) = pixel.color;
+          write!(s, "\x1b[38;2;{r};{g};{b}m").unwrap();
+          current = Some(pixel.color);
+        }
+        s.push(pixel.glyph);
+      }
+      s.push_str("\x1b[0m");
+    }
+    s
+  }
+
+  /// Calls `f` with each row of the image as RGB bytes, with every pixel `scale`×`scale` pixels big.
+  fn scaled_rows(&self, scale: usize, mut f: impl FnMut(&[u8])) {
+    let mut row = Vec::with_capacity(self.width() * scale * 3);
+    for y in 0..self.height() {
+      row.clear();
+      for pixel in self.pixels.row(y) {
+        let Rgb(r, g, b) = pixel.color;
+        for _ in 0..scale {
+          row.extend([r, g, b]);
+        }
+      }
+      for _ in 0..scale {
+        f(&row);
+      }
+    }
+  }
+
+  /// A binary PPM (`P6`) image.
+  pub fn ppm(&self, scale: usize) -> Vec<u8> {
+    let scale = scale.max(1);
+    let (width, height) = (self.width() * scale, self.height() * scale);
+    let mut out = format!("P6\n{width} {height}\n255\n").into_bytes();
+    self.scaled_rows(scale, |row| out.extend_from_slice(row));
+    out
+  }
+
+  /// An 8-bit RGB PNG image.
+  pub fn png(&self, scale: usize) -> Vec<u8> {
+    let scale = scale.max(1);
+    let (width, height) = (self.width() * scale, self.height() * scale);
+
+    let mut header = vec![];
+    header.extend((width as u32).to_be_bytes());
+    header.extend((height as u32).to_be_bytes());
+    // bit depth 8, colour type RGB, default compression, filter and no interlacing
+    header.extend([8, 2, 0, 0, 0]);
+
+    let mut encoder = ZlibEncoder::new(vec![], Compression::default());
+    self.scaled_rows(scale, |row| {
+      // every scanline starts with its filter type, none
+      encoder.write_all(&[0]).unwrap();
+      encoder.write_all(row).unwrap();
+    });
+    let data = encoder.finish().unwrap();
+
+    let mut out = b"\x89PNG\r\n\x1a\n".to_vec();
+    for (kind, chunk) in [(b"IHDR", &header[..]), (b"IDAT", &data), (b"IEND", &[])] {
+      out.extend((chunk.len() as u32).to_be_bytes());
+      let start = out.len();
+      out.extend(kind);
+      out.extend(chunk);
+      let crc = crc32fast::hash(&out[start..]);
+      out.extend(crc.to_be_bytes());
+    }
+    out
+  }
+
+  pub fn render(&self, format: Format, scale: usize) -> Vec<u8> {
+    match format {
+      Format::Ascii => (self.ascii() + "\n").into_bytes(),
+      Format::Ansi => (self.ansi() + "\n").into_bytes(),
+      Format::Ppm => self.ppm(scale),
+      Format::Png => self.png(scale),
+    }
+  }
+}
+
+impl fmt::Display for Image {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.write_str(&self.ascii())
+  }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Format {
+  Ascii,
+  Ansi,
+  Ppm,
+  Png,
+}
+
+impl Format {
+  pub fn extension(self) -> &'static str {
+    match self {
+      Format::Ascii => "txt",
+      Format::Ansi => "ans",
+      Format::Ppm => "ppm",
+      Format::Png => "png",
+    }
+  }
+}
+
+impl FromStr for Format {
+  type Err = anyhow::Error;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    match s {
+      "ascii" => Ok(Format::Ascii),
+      "ansi" => Ok(Format::Ansi),
+      "ppm" => Ok(Format::Ppm),
+      "png" => Ok(Format::Png),
+      _ => anyhow::bail!("unknown format `{s}`, expected one of `ascii`, `ansi`, `ppm` or `png`"),
+    }
+  }
+}
+
+/// Writes frames of an animation to `dir` as `00000.png`, `00001.png` and so on.
+pub struct Recorder {
+  dir: PathBuf,
+  format: Format,
+  scale: usize,
+  every: usize,
+  seen: usize,
+  written: usize,
+}
+
+impl Recorder {
+  /// Creates `dir` if it doesn't exist yet.
+  pub fn new(dir: impl Into<PathBuf>, format: Format) -> anyhow::Result<Self> {
+    let dir = dir.into();
+    fs::create_dir_all(&dir).with_context(|| format!("failed to create `{}`", dir.display()))?;
+    Ok(Self {
+      dir,
+      format,
+      scale: 4,
+      every: 1,
+      seen: 0,
+      written: 0,
+    })
+  }
+
+  /// Size of each cell in pixels, for image formats. Defaults to 4.
+  pub fn scale(mut self, scale: usize) -> Self {
+    self.scale = scale.max(1);
+    self
+  }
+
+  /// Only keep every `n`th frame, for simulations with too many steps to keep them all.
+  pub fn every(mut self, n: usize) -> Self {
+    self.every = n.max(1);
+    self
+  }
+
+  /// Writes the image returned by `render` as the next frame, unless it's skipped by
+  /// [`Recorder::every`], in which case `render` isn't called.
+  pub fn record(&mut self, render: impl FnOnce() -> Image) -> anyhow::Result<()> {
+    let keep = self.seen.is_multiple_of(self.every);
+    self.seen += 1;
+    if keep {
+      self.write(&render())?;
+    }
+    Ok(())
+  }
+
+  /// Writes `image` as the next frame, even if it would be skipped. Use it for the final state.
+  pub fn write(&mut self, image: &Image) -> anyhow::Result<()> {
+    let path = self
+      .dir
+      .join(format!("{:05}.{}", self.written, self.format.extension()));
+    fs::write(&path, image.render(self.format, self.scale))
+      .with_context(|| format!("failed to write `{}`", path.display()))?;
+    self.written += 1;
+    Ok(())
+  }
+
+  /// Number of frames written so far.
+  pub fn frames(&self) -> usize {
+    self.written
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use std::io::Read;
+
+  use flate2::read::ZlibDecoder;
+
+  use super::*;
+
+  fn image() -> Image {
+    Image::from_fn(3, 2, |x, y| match (x + y) % 2 {
+      0 => Pixel::new('#', Rgb::RED),
+      _ => Pixel::new('.', Rgb::BLACK),
+    })
+  }
+
+  #[test]
+  fn text() {
+    assert_eq!(image().ascii(), "#.#\n.#.");
+    assert_eq!(
+      image().ansi(),
+      "\x1b[38;2;220;50;47m#\x1b[38;2;0;0;0m.\x1b[38;2;220;50;47m#\x1b[0m\n\
+       \x1b[38;2;0;0;0m.\x1b[38;2;220;50;47m#\x1b[38;2;0;0;0m.\x1b[0m"
+    );
+  }
+
+  #[test]
+  fn ppm() {
+    let ppm = image().ppm(2);
+    let header = b"P6\n6 4\n255\n";
+    assert_eq!(&ppm[..header.len()], header);
+    assert_eq!(ppm.len(), header.len() + 6 * 4 * 3);
+    // the second pixel of the first row is the first pixel, scaled
+    assert_eq!(&ppm[header.len() + 3..header.len() + 6], [220, 50, 47]);
+    assert_eq!(&ppm[header.len() + 6..header.len() + 9], [0, 0, 0]);
+  }
+
+  #[test]
+  fn png() {
+    let png = image().png(1);
+    assert_eq!(&png[..8], b"\x89PNG\r\n\x1a\n");
+    assert_eq!(&png[12..16], b"IHDR");
+    assert_eq!(&png[16..24], [0, 0, 0, 3, 0, 0, 0, 2]);
+    assert_eq!(&png[png.len() - 8..png.len() - 4], b"IEND");
+
+    // IHDR is 13 bytes, plus length, kind and CRC
+    let idat = 8 + 12 + 13;
+    let len = u32::from_be_bytes(png[idat..idat + 4].try_into().unwrap()) as usize;
+    assert_eq!(&png[idat + 4..idat + 8], b"IDAT");
+    let mut data = vec![];
+    ZlibDecoder::new(&png[idat + 8..idat + 8 + len])
+      .read_to_end(&mut data)
+      .unwrap();
+    assert_eq!(data.len(), 2 * (1 + 3 * 3));
+    assert_eq!(&data[..4], [0, 220, 50, 47]);
+  }
+
+  #[test]
+  fn records_frames() {
+    let dir = std::env::temp_dir().join(format!("aoc-{}-frames", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+
+    let mut recorder = Recorder::new(&dir, Format::Ascii).unwrap().every(2);
+    for _ in 0..5 {
+      recorder.record(image).unwrap();
+    }
+    recorder.write(&image()).unwrap();
+    assert_eq!(recorder.frames(), 4);
+
+    let mut names = fs::read_dir(&dir)
+      .unwrap()
+      .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+      .collect::<Vec<_>>();
+    names.sort();
+    assert_eq!(names, ["00000.txt", "00001.txt", "00002.txt", "00003.txt"]);
+    assert_eq!(
+      fs::read_to_string(dir.join("00000.txt")).unwrap(),
+      "#.#\n.#.\n"
+    );
+  }
+}
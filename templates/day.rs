@@ -0,0 +1,40 @@
+use crate::{ParseError, Solution};
+
+pub struct Day{{day}};
+
+impl Solution for Day{{day}} {
+  type Input<'a> = Vec<&'a str>;
+  type Output1 = usize;
+  type Output2 = usize;
+
+  fn parse(source: &str) -> Result<Self::Input<'_>, ParseError> {
+    Ok(source.lines().collect())
+  }
+
+  fn part1(_input: &Self::Input<'_>) -> Self::Output1 {
+    0
+  }
+
+  fn part2(_input: &Self::Input<'_>) -> Self::Output2 {
+    0
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  const EXAMPLE: &str = include_str!("example.txt");
+
+  #[test]
+  #[ignore = "no example yet"]
+  fn part1() {
+    assert_eq!(Day{{day}}::part1(&Day{{day}}::parse(EXAMPLE).unwrap()), 0);
+  }
+
+  #[test]
+  #[ignore = "no example yet"]
+  fn part2() {
+    assert_eq!(Day{{day}}::part2(&Day{{day}}::parse(EXAMPLE).unwrap()), 0);
+  }
+}